@@ -1,60 +1,227 @@
 use super::languages::AnalysisProvider;
+use super::metrics::{FunctionMetrics, Grammar};
 use crate::analysis::rules::Rule;
 use crate::core::config::LintConfig;
 use crate::core::rules::Smell;
 use std::path::Path;
-use tree_sitter::{Language, Node, Parser};
+use tree_sitter::{Language, Node, Parser, Point, Tree};
 
-/// Un analizador genérico capaz de procesar cualquier lenguaje soportado por Tree-sitter
-/// siempre que se le suministren las reglas y la gramática correspondientes.
+/// A generic analyzer for any language Tree-sitter supports, given its rules
+/// and grammar.
 pub struct GenericAnalyzer {
     language: Language,
+    grammar: &'static Grammar,
     rules: Vec<Box<dyn Rule>>,
 }
 
 impl GenericAnalyzer {
-    pub fn new(language: Language, rules: Vec<Box<dyn Rule>>) -> Self {
-        Self { language, rules }
-    }
-
-    fn traverse(
-        &self,
-        node: Node,
-        source: &str,
-        path: &Path,
-        config: &LintConfig,
-        smells: &mut Vec<Smell>,
-    ) {
-        // 1. Ejecutar reglas en el nodo actual
+    pub fn new(language: Language, grammar: &'static Grammar, rules: Vec<Box<dyn Rule>>) -> Self {
+        Self {
+            language,
+            grammar,
+            rules,
+        }
+    }
+
+    fn parse(&self, code: &str) -> Option<Tree> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&self.language)
+            .expect("Error loading grammar in GenericAnalyzer");
+        parser.parse(code, None)
+    }
+
+    fn traverse(&self, node: Node, walk: &mut Walk) {
+        let scope = self.grammar.scope_name(node, walk.source);
+        if let Some(name) = scope {
+            walk.scope.push(name.to_string());
+        }
+
+        // 1. Run the rules on the current node
         for rule in &self.rules {
-            if let Some(new_smells) = rule.check(node, source, path, config) {
-                smells.extend(new_smells);
+            if let Some(new_smells) = rule.check(node, walk.source, walk.path, walk.config) {
+                let scope = walk.scope.join(self.grammar.separator);
+                let enabled = new_smells
+                    .into_iter()
+                    .filter(|smell| walk.config.rule_enabled(&smell.rule_id));
+                for smell in enabled {
+                    // Text rules look at the root: their symbol comes from the position.
+                    let symbol = if node.parent().is_none() {
+                        self.symbol_at(node, &smell, walk)
+                    } else {
+                        scope.clone()
+                    };
+                    walk.smells
+                        .push(if smell.symbol.is_none() && !symbol.is_empty() {
+                            smell.with_symbol(&symbol)
+                        } else {
+                            smell
+                        });
+                }
             }
         }
 
-        // 2. Descenso recursivo
+        // 2. Recurse into the children
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            self.traverse(child, source, path, config, smells);
+            self.traverse(child, walk);
         }
+
+        if scope.is_some() {
+            walk.scope.pop();
+        }
+    }
+
+    /// Symbol enclosing the start of `smell`: the module plus the scopes
+    /// (functions, classes...) between the root and the node at that position.
+    /// It looks at the previous character, because these rules flag what
+    /// follows the code (whitespace at the end of a line, the end of the file),
+    /// where the enclosing node has already closed.
+    fn symbol_at(&self, root: Node, smell: &Smell, walk: &Walk) -> String {
+        let row = smell.line.saturating_sub(1);
+        let line = walk.source.split('\n').nth(row).unwrap_or("");
+        let column = line
+            .char_indices()
+            .nth(smell.column.saturating_sub(2))
+            .map_or(0, |(byte, _)| byte);
+        let point = Point::new(row, column);
+        let Some(node) = root.descendant_for_point_range(point, point) else {
+            return walk.scope.join(self.grammar.separator);
+        };
+
+        let mut inner: Vec<&str> = std::iter::successors(Some(node), |n| n.parent())
+            .filter_map(|n| self.grammar.scope_name(n, walk.source))
+            .collect();
+        inner.reverse();
+        let mut path: Vec<&str> = walk.scope.iter().map(String::as_str).collect();
+        path.extend(inner);
+        path.join(self.grammar.separator)
     }
 }
 
+/// State of a traversal: what stays the same between nodes and what accumulates.
+struct Walk<'a> {
+    source: &'a str,
+    path: &'a Path,
+    config: &'a LintConfig,
+    smells: Vec<Smell>,
+    /// Symbols enclosing the current node, starting with the module.
+    scope: Vec<String>,
+}
+
 impl AnalysisProvider for GenericAnalyzer {
     fn analyze(&self, path: &Path, code: &str, config: &LintConfig) -> Vec<Smell> {
-        let mut smells = Vec::new();
-        let mut parser = Parser::new();
-
-        parser
-            .set_language(&self.language)
-            .expect("Error loading grammar in GenericAnalyzer");
-
-        let tree = match parser.parse(code, None) {
+        let tree = match self.parse(code) {
             Some(t) => t,
             None => return vec![],
         };
 
-        self.traverse(tree.root_node(), code, path, config, &mut smells);
-        smells
+        let mut walk = Walk {
+            source: code,
+            path,
+            config,
+            smells: Vec::new(),
+            scope: self.grammar.module_path(path),
+        };
+        self.traverse(tree.root_node(), &mut walk);
+        if let Some(lang) = config.lang {
+            for smell in &mut walk.smells {
+                smell.localize(lang);
+            }
+        }
+        walk.smells
+    }
+
+    fn function_metrics(&self, path: &Path, code: &str) -> Vec<FunctionMetrics> {
+        match self.parse(code) {
+            Some(tree) => self.grammar.function_metrics(tree.root_node(), code, path),
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::metrics::{PYTHON, RUST};
+    use crate::analysis::rules;
+
+    fn symbols(analyzer: &GenericAnalyzer, path: &str, code: &str) -> Vec<(String, String)> {
+        let config = LintConfig {
+            rules: [
+                ("trailing_whitespace", true),
+                ("missing_final_newline", true),
+            ]
+            .map(|(id, on)| (id.to_string(), on))
+            .into(),
+            ..LintConfig::default()
+        };
+        analyzer
+            .analyze(Path::new(path), code, &config)
+            .into_iter()
+            .map(|smell| (smell.rule_id, smell.symbol.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_smells_carry_enclosing_symbol() {
+        let rust = GenericAnalyzer::new(
+            tree_sitter_rust::LANGUAGE.into(),
+            &RUST,
+            rules::rust::get_rules(),
+        );
+        let code = r#"
+// TODO: top level
+impl<T> Engine<T> {
+    fn run(&self) {
+        let ab = 1; // TODO: inside
+    }
+}
+"#;
+        assert_eq!(
+            symbols(&rust, "src/analysis/engine.rs", code),
+            [
+                ("todo_comment".into(), "crate::analysis::engine".into()),
+                (
+                    "short_variable".into(),
+                    "crate::analysis::engine::Engine::run".into()
+                ),
+                (
+                    "todo_comment".into(),
+                    "crate::analysis::engine::Engine::run".into()
+                ),
+            ]
+        );
+
+        let python = GenericAnalyzer::new(
+            tree_sitter_python::LANGUAGE.into(),
+            &PYTHON,
+            rules::python::get_rules(),
+        );
+        let code = "class Greeter:\n    def greet(self):\n        # TODO: i18n\n        pass\n";
+        assert_eq!(
+            symbols(&python, "app/models.py", code),
+            [("todo_comment".into(), "app.models.Greeter.greet".into())]
+        );
+    }
+
+    #[test]
+    fn test_text_rules_resolve_symbol_from_position() {
+        let python = GenericAnalyzer::new(
+            tree_sitter_python::LANGUAGE.into(),
+            &PYTHON,
+            rules::python::get_rules(),
+        );
+        let code = "x = 1 \ndef f():\n    return 1 \n\nclass C:\n    def g(self):\n        pass\t\n    \ny = 2";
+        assert_eq!(
+            symbols(&python, "a.py", code),
+            [
+                ("trailing_whitespace".into(), "a".into()),
+                ("trailing_whitespace".into(), "a.f".into()),
+                ("trailing_whitespace".into(), "a.C.g".into()),
+                ("trailing_whitespace".into(), "a".into()),
+                ("missing_final_newline".into(), "a".into()),
+            ]
+        );
     }
 }
@@ -1,40 +1,345 @@
-use super::languages::get_analyzer;
+use super::languages::{get_analyzer, Language, LanguageRegistry};
+use super::metrics::FunctionMetrics;
+use super::walker::{walk_directory, SourceFile, UnsupportedFiles};
 use crate::core::config::LintConfig;
 use crate::core::rules::Smell;
+use anyhow::Result;
 use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
-use tracing::{debug, warn};
-
-/// Orchestrates the analysis process in parallel.
-pub fn run_analysis(files: &[PathBuf], config: &LintConfig) -> Vec<Smell> {
-    files
-        .par_iter()
-        .flat_map(|path| {
-            // 1. Identify Language
-            let ext = path
-                .extension()
-                .and_then(|s| s.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-
-            // 2. Get Analyzer
-            if let Some(analyzer) = get_analyzer(&ext) {
-                // 3. Read File (IO)
-                match fs::read_to_string(path) {
-                    Ok(code) => {
-                        debug!("Analyzing: {:?}", path);
-                        // 4. Analyze
-                        analyzer.analyze(path, &code, config)
-                    }
-                    Err(e) => {
-                        warn!("Could not read file {:?}: {}", path, e);
-                        vec![]
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Files the walker may queue per analysis thread before it waits. Keeps
+/// memory bounded when walking runs ahead of parsing.
+const QUEUED_FILES_PER_THREAD: usize = 16;
+
+/// Result of a full analysis run.
+#[derive(Default)]
+pub struct Analysis {
+    /// All smells found, sorted by file and line. Empty if they were streamed
+    /// without `keep_smells`.
+    pub smells: Vec<Smell>,
+    /// Number of smells found, whether or not they were kept.
+    pub total_smells: usize,
+    /// Files that went through the analysis stage, per language.
+    pub analyzed: BTreeMap<Language, usize>,
+    /// Paths of those files, sorted. Empty if smells were streamed without
+    /// `keep_smells`.
+    pub files: Vec<PathBuf>,
+    /// Source files skipped because their language has no analyzer.
+    pub unsupported: UnsupportedFiles,
+    /// Files that were collected but could not be analyzed.
+    pub errors: Vec<AnalysisError>,
+    /// Per-function metrics, sorted by file and line. Only filled when requested.
+    pub functions: Vec<FunctionMetrics>,
+    /// Wall-clock time of the whole run.
+    pub duration: Duration,
+    /// Source that was analyzed from memory rather than read from disk (e.g.
+    /// `--stdin`), so reports quote what was linted, not the file on disk.
+    pub sources: BTreeMap<PathBuf, String>,
+}
+
+/// A file the engine had to give up on, e.g. because it could not be read.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+/// Hands each file's smells to the caller as soon as the file is analyzed,
+/// in completion order (e.g. for NDJSON output).
+pub struct Stream<'a> {
+    pub on_file: &'a (dyn Fn(&[Smell]) + Sync),
+    /// Also keep the smells in `Analysis::smells`. Turning this off keeps memory
+    /// bounded regardless of how many files and smells the run goes through:
+    /// only counts (and the files that could not be read) are accumulated.
+    pub keep_smells: bool,
+}
+
+/// Optional behaviour of a pipeline run.
+#[derive(Default)]
+pub struct PipelineOptions<'a> {
+    /// Worker threads for walking and analysis (0 = one per CPU).
+    pub jobs: usize,
+    pub stream: Option<Stream<'a>>,
+    /// Also measure every function into `Analysis::functions`.
+    pub metrics: bool,
+}
+
+/// What the analysis threads accumulate. Only the counts grow with the run
+/// unless smells are kept or metrics were requested.
+#[derive(Default)]
+struct Totals {
+    analyzed: BTreeMap<Language, usize>,
+    files: Vec<PathBuf>,
+    total_smells: usize,
+    smells: Vec<Smell>,
+    functions: Vec<FunctionMetrics>,
+    errors: Vec<AnalysisError>,
+}
+
+impl Totals {
+    fn merge(mut self, other: Totals) -> Totals {
+        for (language, count) in other.analyzed {
+            *self.analyzed.entry(language).or_insert(0) += count;
+        }
+        self.files.extend(other.files);
+        self.total_smells += other.total_smells;
+        self.smells.extend(other.smells);
+        self.functions.extend(other.functions);
+        self.errors.extend(other.errors);
+        self
+    }
+}
+
+/// What the analysis of a single file produced.
+pub struct FileOutcome {
+    pub smells: Vec<Smell>,
+    pub functions: Vec<FunctionMetrics>,
+}
+
+impl Analysis {
+    pub fn files_analyzed(&self) -> usize {
+        self.analyzed.values().sum()
+    }
+}
+
+/// Walks and analyzes `targets` with `run_pipeline`, logging progress unless `quiet`.
+pub fn analyze_paths(
+    targets: &[PathBuf],
+    config: &LintConfig,
+    options: PipelineOptions,
+    quiet: bool,
+) -> Result<Analysis> {
+    if !quiet {
+        let shown: Vec<_> = targets.iter().map(|t| t.display().to_string()).collect();
+        info!("Starting lint on: {}", shown.join(", "));
+    }
+
+    let analysis = run_pipeline(targets, config, options)?;
+    if !quiet {
+        info!(
+            "Analyzed {} files{}.",
+            analysis.files_analyzed(),
+            breakdown(&analysis.analyzed)
+        );
+        if !analysis.unsupported.is_empty() {
+            let skipped: usize = analysis.unsupported.values().sum();
+            info!(
+                "Skipped {} files in languages without an analyzer{}.",
+                skipped,
+                breakdown(&analysis.unsupported)
+            );
+        }
+    }
+    Ok(analysis)
+}
+
+/// Formats per-language counts as ` (rust: 3, python: 1)`.
+pub fn breakdown<K: std::fmt::Display>(counts: &BTreeMap<K, usize>) -> String {
+    if counts.is_empty() {
+        return String::new();
+    }
+    let parts: Vec<String> = counts
+        .iter()
+        .map(|(lang, count)| format!("{}: {}", lang, count))
+        .collect();
+    format!(" ({})", parts.join(", "))
+}
+
+/// Walks `roots` and analyzes files in parallel as they are discovered, so
+/// parsing overlaps with directory traversal instead of waiting for it.
+///
+/// `options.jobs` is the thread budget shared by the walker and the analysis
+/// pool (see `split_jobs`). With a `stream`, results are also delivered per
+/// file while the run is going.
+pub fn run_pipeline(
+    roots: &[PathBuf],
+    config: &LintConfig,
+    options: PipelineOptions,
+) -> Result<Analysis> {
+    let PipelineOptions {
+        jobs,
+        stream,
+        metrics,
+    } = options;
+    let started = Instant::now();
+    let (walkers, analyzers) = split_jobs(jobs);
+    let pool = ThreadPoolBuilder::new().num_threads(analyzers).build()?;
+    let (tx, rx) = mpsc::sync_channel::<SourceFile>(analyzers * QUEUED_FILES_PER_THREAD);
+    let registry = LanguageRegistry::new(&config.languages);
+    let registry = &registry;
+
+    let (totals, unsupported) = thread::scope(|scope| {
+        // 1. Producer: the walker feeds files into the channel
+        let walker = scope.spawn(move || walk_directory(roots, walkers, registry, tx));
+
+        // 2. Consumer: rayon drains the channel while the walk is still running.
+        // Each rayon job folds its files into its own totals, merged at the end.
+        let totals = pool.install(|| {
+            rx.into_iter()
+                .par_bridge()
+                .fold(Totals::default, |mut totals, file| {
+                    match analyze_file(&file, config, metrics) {
+                        Ok(outcome) => {
+                            *totals.analyzed.entry(file.language).or_insert(0) += 1;
+                            totals.total_smells += outcome.smells.len();
+                            if let Some(stream) = &stream {
+                                (stream.on_file)(&outcome.smells);
+                            }
+                            if stream.as_ref().is_none_or(|stream| stream.keep_smells) {
+                                totals.files.push(file.path.clone());
+                                totals.smells.extend(outcome.smells);
+                            }
+                            totals.functions.extend(outcome.functions);
+                        }
+                        Err(error) => totals.errors.push(error),
                     }
-                }
-            } else {
-                vec![]
-            }
-        })
-        .collect() // Gather all smells from all threads into a single Vec
+                    totals
+                })
+                .reduce(Totals::default, Totals::merge)
+        });
+
+        // A panicking walker drops the sender, so the analysis above ends
+        // early; re-raise the panic rather than report a partial run as clean.
+        let unsupported = walker
+            .join()
+            .unwrap_or_else(|panic| panic::resume_unwind(panic));
+        (totals, unsupported)
+    });
+    let Totals {
+        analyzed,
+        mut files,
+        total_smells,
+        mut smells,
+        mut functions,
+        mut errors,
+    } = totals;
+
+    // Arrival order depends on thread scheduling; keep the report deterministic.
+    smells.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then_with(|| a.line.cmp(&b.line))
+    });
+
+    functions.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    files.sort();
+    errors.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(Analysis {
+        smells,
+        total_smells,
+        analyzed,
+        files,
+        unsupported,
+        errors,
+        functions,
+        duration: started.elapsed(),
+        sources: BTreeMap::new(),
+    })
+}
+
+/// Splits `jobs` threads (0 = one per CPU) into walker and analysis threads.
+/// Walking is mostly waiting on the file system, so it gets one thread in four;
+/// each side needs at least one, so the smallest budget is two.
+fn split_jobs(jobs: usize) -> (usize, usize) {
+    let total = match jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        jobs => jobs,
+    };
+    let walkers = (total / 4).max(1);
+    (walkers, total.saturating_sub(walkers).max(1))
+}
+
+/// Analyzes a single file from disk, measuring its functions too if `metrics` is set.
+pub fn analyze_file(
+    file: &SourceFile,
+    config: &LintConfig,
+    metrics: bool,
+) -> Result<FileOutcome, AnalysisError> {
+    let (path, language) = (file.path.as_path(), file.language);
+
+    // 1. Get Analyzer (the walker already identified the language)
+    let analyzer = get_analyzer(language);
+
+    // 2. Read File (IO)
+    match fs::read_to_string(path) {
+        Ok(code) => {
+            debug!("Analyzing ({}): {:?}", language, path);
+            // 3. Analyze
+            Ok(FileOutcome {
+                smells: analyzer.analyze(path, &code, config),
+                functions: if metrics {
+                    analyzer.function_metrics(path, &code)
+                } else {
+                    vec![]
+                },
+            })
+        }
+        Err(e) => {
+            warn!("Could not read file {:?}: {}", path, e);
+            Err(AnalysisError {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })
+        }
+    }
+}
+
+/// Analyzes in-memory source as if it were the file at `path` (e.g. an unsaved
+/// editor buffer). The file does not need to exist on disk.
+///
+/// Returns `None` when no analyzer handles the language of `path`.
+pub fn analyze_source(
+    path: &Path,
+    code: &str,
+    config: &LintConfig,
+    metrics: bool,
+) -> Option<Analysis> {
+    let started = Instant::now();
+    let registry = LanguageRegistry::new(&config.languages);
+    let language = registry.detect_source(path, code)?;
+    debug!("Analyzing buffer ({}) as: {:?}", language, path);
+    let analyzer = get_analyzer(language);
+    let smells = analyzer.analyze(path, code, config);
+    Some(Analysis {
+        total_smells: smells.len(),
+        smells,
+        functions: if metrics {
+            analyzer.function_metrics(path, code)
+        } else {
+            vec![]
+        },
+        analyzed: BTreeMap::from([(language, 1)]),
+        files: vec![path.to_path_buf()],
+        duration: started.elapsed(),
+        sources: BTreeMap::from([(path.to_path_buf(), code.to_string())]),
+        ..Analysis::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_is_a_total_budget() {
+        assert_eq!(split_jobs(1), (1, 1));
+        assert_eq!(split_jobs(4), (1, 3));
+        assert_eq!(split_jobs(16), (4, 12));
+        let (walkers, analyzers) = split_jobs(0);
+        assert!(walkers >= 1 && analyzers >= 1);
+    }
 }
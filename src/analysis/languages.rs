@@ -1,26 +1,305 @@
 use super::analyzer::GenericAnalyzer;
-use crate::core::config::LintConfig;
+use super::metrics::{self, FunctionMetrics};
+use crate::core::config::{LanguageConfig, LintConfig};
 use crate::core::rules::Smell;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
+use tracing::warn;
 
 /// Trait that every language analyzer must implement.
 pub trait AnalysisProvider: Send + Sync {
     fn analyze(&self, path: &Path, code: &str, config: &LintConfig) -> Vec<Smell>;
+
+    /// Per-function size and complexity, as the rules measure them.
+    fn function_metrics(&self, path: &Path, code: &str) -> Vec<FunctionMetrics>;
+}
+
+/// Languages with a registered analyzer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Rust,
+    Python,
+}
+
+impl Language {
+    /// Parses the name used in configuration files (`"rust"`, `"python"`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "rust" => Some(Language::Rust),
+            "python" => Some(Language::Python),
+            _ => None,
+        }
+    }
+
+    /// Maps a shebang interpreter (`python3.11`, `pypy3`, `rust-script`) to a language.
+    fn from_interpreter(interpreter: &str) -> Option<Self> {
+        let base = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        match base {
+            "python" | "pypy" => Some(Language::Python),
+            "rust-script" | "run-cargo-script" => Some(Language::Rust),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Language::Rust => "rust",
+            Language::Python => "python",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Built-in extension mappings; `[languages.extensions]` in the config extends them.
+const DEFAULT_EXTENSIONS: &[(&str, Language)] = &[
+    ("rs", Language::Rust),
+    ("py", Language::Python),
+    ("pyi", Language::Python),
+    ("pyw", Language::Python),
+];
+
+/// Built-in file name mappings for extensionless build scripts.
+const DEFAULT_FILENAMES: &[(&str, Language)] = &[
+    ("SConstruct", Language::Python),
+    ("SConscript", Language::Python),
+];
+
+/// Source languages we recognize but cannot analyze yet. Only used to report
+/// what was skipped; these files never reach the engine.
+const UNSUPPORTED_EXTENSIONS: &[(&str, &str)] = &[
+    ("js", "javascript"),
+    ("jsx", "javascript"),
+    ("ts", "typescript"),
+    ("tsx", "typescript"),
+    ("go", "go"),
+    ("java", "java"),
+    ("c", "c"),
+    ("h", "c"),
+    ("cpp", "c++"),
+    ("hpp", "c++"),
+    ("cs", "c#"),
+    ("php", "php"),
+    ("rb", "ruby"),
+];
+
+/// A shebang longer than this is not worth reading.
+const MAX_SHEBANG_LEN: u64 = 256;
+
+/// Resolves the language of a file from its name, its extension or, for
+/// extensionless scripts, its shebang line. Shared by the walker and the engine
+/// so both agree on what gets analyzed.
+pub struct LanguageRegistry {
+    extensions: HashMap<String, Language>,
+    filenames: HashMap<String, Language>,
+}
+
+impl LanguageRegistry {
+    pub fn new(config: &LanguageConfig) -> Self {
+        let mut extensions: HashMap<String, Language> = DEFAULT_EXTENSIONS
+            .iter()
+            .map(|(ext, lang)| (ext.to_string(), *lang))
+            .collect();
+        let mut filenames: HashMap<String, Language> = DEFAULT_FILENAMES
+            .iter()
+            .map(|(name, lang)| (name.to_string(), *lang))
+            .collect();
+
+        for (ext, name) in &config.extensions {
+            if let Some(lang) = configured_language(ext, name) {
+                extensions.insert(ext.trim_start_matches('.').to_lowercase(), lang);
+            }
+        }
+        for (file, name) in &config.filenames {
+            if let Some(lang) = configured_language(file, name) {
+                filenames.insert(file.clone(), lang);
+            }
+        }
+
+        Self {
+            extensions,
+            filenames,
+        }
+    }
+
+    /// Detects the language of a file on disk, reading its shebang if needed.
+    pub fn detect(&self, path: &Path) -> Option<Language> {
+        self.detect_by_name(path).or_else(|| {
+            if path.extension().is_some() {
+                return None;
+            }
+            read_shebang(path).and_then(|line| shebang_language(&line))
+        })
+    }
+
+    /// Detects the language of an in-memory buffer that claims to be `path`.
+    pub fn detect_source(&self, path: &Path, code: &str) -> Option<Language> {
+        self.detect_by_name(path).or_else(|| {
+            if path.extension().is_some() {
+                return None;
+            }
+            code.lines().next().and_then(shebang_language)
+        })
+    }
+
+    /// Names the language of a source file that has no analyzer (e.g. `"go"`),
+    /// or `None` if the file is not source code we know about.
+    pub fn unsupported_language(&self, path: &Path) -> Option<&'static str> {
+        let ext = path.extension().and_then(|s| s.to_str())?.to_lowercase();
+        UNSUPPORTED_EXTENSIONS
+            .iter()
+            .find(|(known, _)| *known == ext)
+            .map(|(_, name)| *name)
+    }
+
+    fn detect_by_name(&self, path: &Path) -> Option<Language> {
+        let file_name = path.file_name().and_then(|s| s.to_str())?;
+        if let Some(lang) = self.filenames.get(file_name) {
+            return Some(*lang);
+        }
+        let ext = path.extension().and_then(|s| s.to_str())?;
+        self.extensions.get(&ext.to_lowercase()).copied()
+    }
 }
 
-/// Factory to get the correct analyzer based on file extension.
-pub fn get_analyzer(extension: &str) -> Option<Box<dyn AnalysisProvider>> {
-    match extension {
-        "rs" => {
+/// Factory to get the correct analyzer for a language.
+pub fn get_analyzer(language: Language) -> Box<dyn AnalysisProvider> {
+    match language {
+        Language::Rust => {
             let lang = tree_sitter_rust::LANGUAGE;
             let rules = super::rules::rust::get_rules();
-            Some(Box::new(GenericAnalyzer::new(lang.into(), rules)))
+            Box::new(GenericAnalyzer::new(lang.into(), &metrics::RUST, rules))
         }
-        "py" => {
+        Language::Python => {
             let lang = tree_sitter_python::LANGUAGE;
             let rules = super::rules::python::get_rules();
-            Some(Box::new(GenericAnalyzer::new(lang.into(), rules)))
+            Box::new(GenericAnalyzer::new(lang.into(), &metrics::PYTHON, rules))
+        }
+    }
+}
+
+fn configured_language(key: &str, name: &str) -> Option<Language> {
+    let lang = Language::from_name(name);
+    if lang.is_none() {
+        warn!(
+            "Ignoring language mapping {:?} = {:?}: unknown language",
+            key, name
+        );
+    }
+    lang
+}
+
+/// Reads the `#!` line of `path`. Files that do not start with `#!` (most
+/// extensionless files: `LICENSE`, `Makefile`, binaries) cost a two-byte read.
+fn read_shebang(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let mut magic = [0; 2];
+    file.read_exact(&mut magic).ok()?;
+    if &magic != b"#!" {
+        return None;
+    }
+    let mut line = String::from("#!");
+    BufReader::new(file.take(MAX_SHEBANG_LEN - 2))
+        .read_line(&mut line)
+        .ok()?;
+    Some(line)
+}
+
+/// Extracts the language from a `#!` line such as `#!/usr/bin/env -S python3 -u`.
+fn shebang_language(line: &str) -> Option<Language> {
+    let command = line.strip_prefix("#!")?;
+    let mut words = command.split_whitespace();
+    let program = words.next()?.rsplit('/').next()?;
+
+    let interpreter = if program == "env" {
+        // Skip env flags (`-S`) and variable assignments (`PYTHONPATH=.`)
+        words.find(|w| !w.starts_with('-') && !w.contains('='))?
+    } else {
+        program
+    };
+    Language::from_interpreter(interpreter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_shebang_detection() {
+        let cases = [
+            ("#!/usr/bin/env python3", Some(Language::Python)),
+            ("#!/usr/bin/python3.11 -u", Some(Language::Python)),
+            (
+                "#!/usr/bin/env -S PYTHONPATH=. pypy3",
+                Some(Language::Python),
+            ),
+            ("#!/usr/bin/env rust-script", Some(Language::Rust)),
+            ("#!/bin/sh", None),
+            ("#![allow(dead_code)]", None),
+            ("import os", None),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(shebang_language(line), expected, "{}", line);
         }
-        _ => None,
+    }
+
+    #[test]
+    fn test_extension_mapping_is_configurable() {
+        let mut extensions = BTreeMap::new();
+        extensions.insert(".pyx".to_string(), "python".to_string());
+        extensions.insert("foo".to_string(), "cobol".to_string());
+        let config = LanguageConfig {
+            extensions,
+            ..LanguageConfig::default()
+        };
+        let registry = LanguageRegistry::new(&config);
+
+        let detect = |name: &str| registry.detect_source(Path::new(name), "");
+        assert_eq!(detect("stubs/os.pyi"), Some(Language::Python));
+        assert_eq!(detect("fast.PYX"), Some(Language::Python));
+        assert_eq!(detect("main.rs"), Some(Language::Rust));
+        assert_eq!(detect("legacy.foo"), None);
+        assert_eq!(detect("SConstruct"), Some(Language::Python));
+    }
+
+    #[test]
+    fn test_shebang_only_applies_to_extensionless_files() {
+        let registry = LanguageRegistry::new(&LanguageConfig::default());
+        let script = "#!/usr/bin/env python3\nprint('hi')\n";
+
+        assert_eq!(
+            registry.detect_source(Path::new("bin/deploy"), script),
+            Some(Language::Python)
+        );
+        assert_eq!(registry.detect_source(Path::new("notes.txt"), script), None);
+
+        let dir = std::env::temp_dir().join(format!("lint-shebang-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("deploy"), script).unwrap();
+        std::fs::write(dir.join("LICENSE"), "MIT License\n").unwrap();
+        std::fs::write(dir.join("empty"), "#").unwrap();
+        let detect = |name: &str| registry.detect(&dir.join(name));
+        let found = (detect("deploy"), detect("LICENSE"), detect("empty"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(found, (Some(Language::Python), None, None));
+    }
+
+    #[test]
+    fn test_unsupported_languages_are_named() {
+        let registry = LanguageRegistry::new(&LanguageConfig::default());
+
+        assert_eq!(
+            registry.unsupported_language(Path::new("app.TSX")),
+            Some("typescript")
+        );
+        assert_eq!(registry.unsupported_language(Path::new("main.rs")), None);
+        assert_eq!(registry.unsupported_language(Path::new("README.md")), None);
     }
 }
@@ -0,0 +1,551 @@
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::Node;
+
+/// Size and complexity figures of one function, measured the same way the
+/// bloat and complexity rules measure them.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionMetrics {
+    pub file_path: PathBuf,
+    pub name: String,
+    /// Line where the function starts (base 1).
+    pub line: usize,
+    pub lines: usize,
+    pub params: usize,
+    /// Deepest nesting inside the body, nested functions included (as in `deep_nesting`).
+    pub nesting: usize,
+    /// Cyclomatic complexity: 1 + number of decision points.
+    pub complexity: usize,
+}
+
+/// Node kinds of a tree-sitter grammar that the metrics depend on.
+pub struct Grammar {
+    /// Function definitions.
+    pub functions: &'static [&'static str],
+    /// Keyword shown for a function nested inside another one.
+    pub function_keyword: &'static str,
+    /// Every construct that opens a block and so adds a nesting level, with the
+    /// keyword shown in reports. Functions nested in functions also add a level.
+    pub nesting: &'static [(&'static str, &'static str)],
+    /// `(holder, construct)`: a construct directly inside `holder` continues a
+    /// chain (`else if`) instead of nesting one level deeper.
+    pub chain: Option<(&'static str, &'static str)>,
+    /// Constructs that add one decision point each.
+    pub branches: &'static [&'static str],
+    /// Multi-way constructs and their arms: `n` arms add `n - 1` decision points.
+    pub switch: (&'static str, &'static str),
+    /// Short-circuit operators (`&&`, `or`) and the node kind that holds them.
+    pub logical: (&'static str, &'static [&'static str]),
+    /// Items that open a named scope, for the symbol path of each smell.
+    pub scopes: &'static [&'static str],
+    /// Separator between the parts of a symbol path.
+    pub separator: &'static str,
+    count_params: fn(Node, &str) -> usize,
+    module_path: fn(&Path) -> Vec<String>,
+}
+
+pub const RUST: Grammar = Grammar {
+    functions: &["function_item"],
+    function_keyword: "fn",
+    // `if let` and `while let` are `if_expression`/`while_expression` with a
+    // `let_condition`, so they need no entry of their own.
+    nesting: &[
+        ("if_expression", "if"),
+        ("for_expression", "for"),
+        ("loop_expression", "loop"),
+        ("while_expression", "while"),
+        ("match_expression", "match"),
+        ("closure_expression", "closure"),
+        ("async_block", "async"),
+        ("unsafe_block", "unsafe"),
+        ("const_block", "const"),
+        ("gen_block", "gen"),
+        ("try_block", "try"),
+    ],
+    chain: Some(("else_clause", "if_expression")),
+    branches: &["if_expression", "for_expression", "while_expression"],
+    switch: ("match_expression", "match_arm"),
+    logical: ("binary_expression", &["&&", "||"]),
+    scopes: &["mod_item", "trait_item", "impl_item", "function_item"],
+    separator: "::",
+    count_params: |params, _| params.named_child_count(),
+    module_path: rust_module_path,
+};
+
+pub const PYTHON: Grammar = Grammar {
+    functions: &["function_definition"],
+    function_keyword: "def",
+    // `elif`, `else`, `except` and `case` are clauses of the statement they
+    // belong to: they sit at the same level as its `if`, `try` or `match`.
+    nesting: &[
+        ("if_statement", "if"),
+        ("for_statement", "for"),
+        ("while_statement", "while"),
+        ("try_statement", "try"),
+        ("with_statement", "with"),
+        ("match_statement", "match"),
+        ("lambda", "lambda"),
+        ("list_comprehension", "comprehension"),
+        ("set_comprehension", "comprehension"),
+        ("dictionary_comprehension", "comprehension"),
+        ("generator_expression", "generator"),
+    ],
+    chain: None,
+    branches: &[
+        "if_statement",
+        "elif_clause",
+        "for_statement",
+        "while_statement",
+        "except_clause",
+        "conditional_expression",
+        "if_clause",
+    ],
+    switch: ("match_statement", "case_clause"),
+    logical: ("boolean_operator", &["and", "or"]),
+    scopes: &["class_definition", "function_definition"],
+    separator: ".",
+    count_params: python_params,
+    module_path: python_module_path,
+};
+
+/// `self` and `cls` do not count: the caller does not choose them.
+fn python_params(params: Node, source: &str) -> usize {
+    let mut cursor = params.walk();
+    params
+        .children(&mut cursor)
+        .filter(|child| {
+            matches!(
+                child.kind(),
+                "identifier" | "typed_parameter" | "default_parameter"
+            )
+        })
+        .filter(|child| {
+            let name = child.utf8_text(source.as_bytes()).unwrap_or("?");
+            name != "self" && name != "cls"
+        })
+        .count()
+}
+
+/// `src/analysis/engine.rs` -> `crate::analysis::engine`. Binaries under
+/// `src/bin` and the files under `tests`, `examples` and `benches` are crates
+/// of their own, named after the file: `tests/api.rs` -> `api`. Anywhere else
+/// only the file itself is known to be a module.
+fn rust_module_path(path: &Path) -> Vec<String> {
+    let parts = path_parts(path);
+    let mut module = match parts.iter().rposition(|part| part == "src") {
+        Some(src) => match &parts[src + 1..] {
+            [bin, binary @ ..] if bin == "bin" && !binary.is_empty() => binary.to_vec(),
+            rest => std::iter::once("crate".to_string())
+                .chain(rest.iter().cloned())
+                .collect(),
+        },
+        None => match parts
+            .iter()
+            .rposition(|part| matches!(part.as_str(), "tests" | "examples" | "benches"))
+        {
+            Some(root) => parts[root + 1..].to_vec(),
+            None => parts[parts.len().saturating_sub(1)..].to_vec(),
+        },
+    };
+    if module
+        .last()
+        .is_some_and(|last| matches!(last.as_str(), "main" | "lib" | "mod"))
+        && module.len() > 1
+    {
+        module.pop();
+    }
+    module
+}
+
+/// `pkg/sub/models.py` -> `pkg.sub.models`; `pkg/__init__.py` -> `pkg`. With
+/// a `src` layout the package starts after `src`.
+fn python_module_path(path: &Path) -> Vec<String> {
+    let parts = path_parts(path);
+    let start = parts
+        .iter()
+        .rposition(|part| part == "src")
+        .map_or(0, |src| src + 1);
+    let mut module = parts[start..].to_vec();
+    if module.last().is_some_and(|last| last == "__init__") && module.len() > 1 {
+        module.pop();
+    }
+    module
+}
+
+/// Components of `path` without its extension, skipping `.`, `/` and `..`.
+fn path_parts(path: &Path) -> Vec<String> {
+    path.with_extension("")
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Grammar {
+    pub fn is_function(&self, kind: &str) -> bool {
+        self.functions.contains(&kind)
+    }
+
+    /// Whether `node` opens a nesting level. Nested functions are left to the
+    /// caller, since they only count from within another function.
+    pub fn opens_level(&self, node: Node) -> bool {
+        let continues_chain = self.chain.is_some_and(|(holder, construct)| {
+            node.kind() == construct && node.parent().is_some_and(|p| p.kind() == holder)
+        });
+        // `is_named`: Python's `lambda` keyword token has the same kind as the node.
+        node.is_named()
+            && !continues_chain
+            && self.nesting.iter().any(|(kind, _)| *kind == node.kind())
+    }
+
+    /// Keyword of a nesting construct or nested function, for reports.
+    pub fn construct_keyword(&self, node: Node) -> &'static str {
+        if self.is_function(node.kind()) {
+            return self.function_keyword;
+        }
+        self.nesting
+            .iter()
+            .find(|(kind, _)| *kind == node.kind())
+            .map_or("block", |(_, keyword)| keyword)
+    }
+
+    /// Symbol path of the module a file defines.
+    pub fn module_path(&self, path: &Path) -> Vec<String> {
+        (self.module_path)(path)
+    }
+
+    /// Name `node` adds to the symbol path, if it opens a scope. Impl blocks
+    /// are named after their type, without generic arguments.
+    pub fn scope_name<'a>(&self, node: Node, source: &'a str) -> Option<&'a str> {
+        if !self.scopes.contains(&node.kind()) {
+            return None;
+        }
+        let name = node
+            .child_by_field_name("name")
+            .or_else(|| node.child_by_field_name("type"))?
+            .utf8_text(source.as_bytes())
+            .ok()?;
+        Some(name.split('<').next().unwrap_or(name).trim())
+    }
+
+    /// Parameters in a function's `parameters` node.
+    pub fn param_count(&self, params: Node, source: &str) -> usize {
+        (self.count_params)(params, source)
+    }
+
+    /// Chain of nesting constructs down to the deepest point under `node`
+    /// (the first one found on ties). Inside a function, nested functions add a
+    /// level like any other block; from the top level of a file they are left
+    /// out, since each one is measured on its own.
+    pub fn deepest_nesting<'t>(&self, node: Node<'t>) -> Vec<Node<'t>> {
+        let walk = Deepest {
+            grammar: self,
+            into_functions: self.is_function(node.kind()),
+        };
+        let mut chain = Vec::new();
+        let mut deepest = Vec::new();
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            walk.visit(child, &mut chain, &mut deepest);
+        }
+        deepest
+    }
+
+    /// Measures every function in the tree rooted at `root`, in source order.
+    pub fn function_metrics(&self, root: Node, source: &str, path: &Path) -> Vec<FunctionMetrics> {
+        let mut functions = Vec::new();
+        self.collect_functions(root, source, path, &mut functions);
+        functions
+    }
+
+    fn collect_functions(
+        &self,
+        node: Node,
+        source: &str,
+        path: &Path,
+        out: &mut Vec<FunctionMetrics>,
+    ) {
+        if self.is_function(node.kind()) {
+            let mut decisions = 0;
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                self.count_decisions(child, &mut decisions);
+            }
+            out.push(FunctionMetrics {
+                file_path: path.to_path_buf(),
+                name: function_name(node, source).to_string(),
+                line: node.start_position().row + 1,
+                lines: function_lines(node),
+                params: node
+                    .child_by_field_name("parameters")
+                    .map_or(0, |params| self.param_count(params, source)),
+                // The same chain `deep_nesting` reports, nested functions included.
+                nesting: self.deepest_nesting(node).len(),
+                complexity: (1 + decisions).max(1) as usize,
+            });
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_functions(child, source, path, out);
+        }
+    }
+
+    /// Walks the body without entering nested functions, which are measured on their own.
+    fn count_decisions(&self, node: Node, decisions: &mut isize) {
+        let kind = node.kind();
+        if self.is_function(kind) {
+            return;
+        }
+
+        if self.branches.contains(&kind) || kind == self.switch.1 {
+            *decisions += 1;
+        }
+        if kind == self.switch.0 {
+            *decisions -= 1;
+        }
+        let (holder, operators) = self.logical;
+        if operators.contains(&kind) && node.parent().is_some_and(|p| p.kind() == holder) {
+            *decisions += 1;
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.count_decisions(child, decisions);
+        }
+    }
+}
+
+/// Walk behind `Grammar::deepest_nesting`.
+struct Deepest<'g> {
+    grammar: &'g Grammar,
+    into_functions: bool,
+}
+
+impl Deepest<'_> {
+    fn visit<'t>(&self, node: Node<'t>, chain: &mut Vec<Node<'t>>, deepest: &mut Vec<Node<'t>>) {
+        let function = self.grammar.is_function(node.kind());
+        if function && !self.into_functions {
+            return;
+        }
+        let nesting = function || self.grammar.opens_level(node);
+        if nesting {
+            chain.push(node);
+            if chain.len() > deepest.len() {
+                deepest.clone_from(chain);
+            }
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.visit(child, chain, deepest);
+        }
+        if nesting {
+            chain.pop();
+        }
+    }
+}
+
+/// Declared name of the function, or `anonymous`.
+pub fn function_name<'a>(node: Node, source: &'a str) -> &'a str {
+    node.child_by_field_name("name")
+        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+        .unwrap_or("anonymous")
+}
+
+/// Lines the function spans, not counting the closing one.
+pub fn function_lines(node: Node) -> usize {
+    let start = node.start_position().row;
+    let end = node.end_position().row;
+    end.saturating_sub(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    fn measure(
+        grammar: &Grammar,
+        language: tree_sitter::Language,
+        code: &str,
+    ) -> Vec<FunctionMetrics> {
+        let mut parser = Parser::new();
+        parser.set_language(&language).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        grammar.function_metrics(tree.root_node(), code, Path::new("test"))
+    }
+
+    /// Keywords on the deepest nesting chain of the first function in `code`.
+    fn nesting_chain(grammar: &Grammar, language: tree_sitter::Language, code: &str) -> String {
+        let mut parser = Parser::new();
+        parser.set_language(&language).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let mut function = tree.root_node();
+        while !grammar.is_function(function.kind()) {
+            function = function.named_child(0).expect("fixture has a function");
+        }
+        grammar
+            .deepest_nesting(function)
+            .into_iter()
+            .map(|construct| grammar.construct_keyword(construct))
+            .collect::<Vec<_>>()
+            .join(" > ")
+    }
+
+    #[test]
+    fn test_rust_nesting_constructs() {
+        let fixtures = [
+            ("fn f() { if let Some(a) = b { go(a); } }", "if"),
+            (
+                "fn f() { while let Some(a) = it.next() { go(a); } }",
+                "while",
+            ),
+            (
+                "fn f() { loop { match a { _ => for i in v {} } } }",
+                "loop > match > for",
+            ),
+            (
+                "fn f() { if a {} else if b { if c {} } else { if d {} } }",
+                "if > if",
+            ),
+            ("fn f() { let g = |x| { if x {} }; }", "closure > if"),
+            ("fn f() { let t = async { loop {} }; }", "async > loop"),
+            ("fn f() { unsafe { for i in v {} } }", "unsafe > for"),
+            (
+                "fn f() { let a = const { if X { 1 } else { 2 } }; }",
+                "const > if",
+            ),
+            (
+                "fn f() { fn inner() { match x { _ => {} } } }",
+                "fn > match",
+            ),
+            ("fn f() { let r = try { if a {} }; }", "try > if"),
+            ("fn f() { let g = gen { loop {} }; }", "gen > loop"),
+        ];
+        for (code, expected) in fixtures {
+            let chain = nesting_chain(&RUST, tree_sitter_rust::LANGUAGE.into(), code);
+            assert_eq!(chain, expected, "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_python_nesting_constructs() {
+        let fixtures = [
+            ("def f():\n    with a:\n        if b:\n            pass\n", "with > if"),
+            (
+                "def f():\n    match a:\n        case 1:\n            if b:\n                pass\n",
+                "match > if",
+            ),
+            (
+                "def f():\n    if a:\n        pass\n    elif b:\n        for c in d:\n            pass\n    else:\n        pass\n",
+                "if > for",
+            ),
+            (
+                "def f():\n    try:\n        pass\n    except E:\n        while a:\n            pass\n",
+                "try > while",
+            ),
+            ("def f():\n    def g():\n        if a:\n            pass\n", "def > if"),
+            ("def f():\n    g = lambda: [y for y in z]\n", "lambda > comprehension"),
+            ("def f():\n    return {k: {v for v in k} for k in a}\n", "comprehension > comprehension"),
+            ("def f():\n    for a in b:\n        yield sum(x for x in a)\n", "for > generator"),
+        ];
+        for (code, expected) in fixtures {
+            let chain = nesting_chain(&PYTHON, tree_sitter_python::LANGUAGE.into(), code);
+            assert_eq!(chain, expected, "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_rust_function_metrics() {
+        let code = r#"
+fn classify(a: i32, b: i32) -> &'static str {
+    if a > 0 && b > 0 {
+        for _ in 0..a {
+            match b {
+                1 => return "one",
+                2 => return "two",
+                _ => {}
+            }
+        }
+    }
+    fn helper() {}
+    "other"
+}
+"#;
+        let metrics = measure(&RUST, tree_sitter_rust::LANGUAGE.into(), code);
+        assert_eq!(metrics.len(), 2);
+
+        let classify = &metrics[0];
+        assert_eq!(classify.name, "classify");
+        assert_eq!(classify.line, 2);
+        assert_eq!(classify.lines, 12);
+        assert_eq!(classify.params, 2);
+        assert_eq!(classify.nesting, 3);
+        // if + && + for + (3 arms - 1)
+        assert_eq!(classify.complexity, 6);
+
+        assert_eq!(metrics[1].name, "helper");
+        assert_eq!(metrics[1].complexity, 1);
+        assert_eq!(metrics[1].nesting, 0);
+    }
+
+    #[test]
+    fn test_nesting_metric_counts_nested_functions() {
+        let code = "fn outer() { if a { fn inner() { loop { match x { _ => {} } } } } }";
+        let metrics = measure(&RUST, tree_sitter_rust::LANGUAGE.into(), code);
+        assert_eq!(
+            (metrics[0].nesting, metrics[1].nesting),
+            (4, 2),
+            "if > fn > loop > match, as deep_nesting reports it"
+        );
+    }
+
+    #[test]
+    fn test_python_function_metrics() {
+        let code = r#"
+class Greeter:
+    def greet(self, name, loud=False):
+        if loud or not name:
+            return name.upper()
+        elif name:
+            try:
+                return [c for c in name if c.isalpha()]
+            except ValueError:
+                pass
+        return name
+"#;
+        let metrics = measure(&PYTHON, tree_sitter_python::LANGUAGE.into(), code);
+        assert_eq!(metrics.len(), 1);
+
+        let greet = &metrics[0];
+        assert_eq!(greet.name, "greet");
+        assert_eq!(greet.params, 2, "self is not counted");
+        assert_eq!(greet.nesting, 3, "if > try > comprehension");
+        // if + or + elif + if_clause + except
+        assert_eq!(greet.complexity, 6);
+    }
+
+    #[test]
+    fn test_module_paths() {
+        let rust = |path: &str| RUST.module_path(Path::new(path)).join("::");
+        assert_eq!(rust("./src/analysis/engine.rs"), "crate::analysis::engine");
+        assert_eq!(rust("src/analysis/mod.rs"), "crate::analysis");
+        assert_eq!(rust("/repo/src/main.rs"), "crate");
+        assert_eq!(rust("examples/demo.rs"), "demo");
+        assert_eq!(rust("src/bin/tool.rs"), "tool");
+        assert_eq!(rust("src/bin/tool/main.rs"), "tool");
+        assert_eq!(rust("src/bin/tool/args.rs"), "tool::args");
+        assert_eq!(rust("tests/api.rs"), "api");
+        assert_eq!(rust("tests/common/mod.rs"), "common");
+        assert_eq!(rust("src/tests/fixtures.rs"), "crate::tests::fixtures");
+
+        let python = |path: &str| PYTHON.module_path(Path::new(path)).join(".");
+        assert_eq!(python("app/models.py"), "app.models");
+        assert_eq!(python("./pkg/sub/models.py"), "pkg.sub.models");
+        assert_eq!(python("pkg/sub/__init__.py"), "pkg.sub");
+        assert_eq!(python("src/pkg/models.py"), "pkg.models");
+        assert_ne!(python("a/models.py"), python("b/models.py"));
+    }
+}
@@ -1,7 +1,6 @@
 pub mod analyzer;
 pub mod engine;
 pub mod languages;
+pub mod metrics;
 pub mod rules;
 pub mod walker;
-
-pub use walker::walk_directory;
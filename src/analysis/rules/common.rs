@@ -0,0 +1,156 @@
+use crate::analysis::rules::{line_ending, Rule};
+use crate::core::config::LintConfig;
+use crate::core::fix::{Edit, Fix};
+use crate::core::messages::MessageArgs;
+use crate::core::rules::{Smell, SmellCategory, Span};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Spaces or tabs at the end of a line. It works on the text, so it only looks
+/// at the root; the tree keeps it out of string literals.
+pub struct TrailingWhitespaceRule;
+
+impl Rule for TrailingWhitespaceRule {
+    fn check(
+        &self,
+        node: Node,
+        source: &str,
+        path: &Path,
+        _config: &LintConfig,
+    ) -> Option<Vec<Smell>> {
+        if node.parent().is_some() {
+            return None;
+        }
+
+        let mut smells = Vec::new();
+        let mut offset = 0;
+        for (row, line) in source.split_inclusive('\n').enumerate() {
+            let start = offset;
+            offset += line.len();
+            let content = line.trim_end_matches('\n').trim_end_matches('\r');
+            let trimmed = content.trim_end_matches([' ', '\t']);
+            if trimmed.len() == content.len() {
+                continue;
+            }
+            let (from, to) = (start + trimmed.len(), start + content.len());
+            if inside_string(node, from, to) {
+                continue;
+            }
+
+            smells.push(
+                Smell::from_catalog(
+                    path.to_path_buf(),
+                    row + 1,
+                    SmellCategory::Hygiene,
+                    "trailing_whitespace",
+                    MessageArgs::new(),
+                )
+                .with_span(Span {
+                    line: row + 1,
+                    column: trimmed.chars().count() + 1,
+                    end_line: row + 1,
+                    end_column: content.chars().count() + 1,
+                })
+                .with_fix(Fix::new(
+                    "Remove trailing whitespace",
+                    vec![Edit::delete(from, to)],
+                )),
+            );
+        }
+
+        if smells.is_empty() {
+            None
+        } else {
+            Some(smells)
+        }
+    }
+}
+
+/// The range falls inside a string literal (multi-line ones included).
+fn inside_string(root: Node, from: usize, to: usize) -> bool {
+    let Some(node) = root.descendant_for_byte_range(from, to) else {
+        return false;
+    };
+    std::iter::successors(Some(node), |n| n.parent()).any(|n| n.kind().contains("string"))
+}
+
+/// A file that does not end with a line break.
+pub struct FinalNewlineRule;
+
+impl Rule for FinalNewlineRule {
+    fn check(
+        &self,
+        node: Node,
+        source: &str,
+        path: &Path,
+        _config: &LintConfig,
+    ) -> Option<Vec<Smell>> {
+        if node.parent().is_some() || source.is_empty() || source.ends_with('\n') {
+            return None;
+        }
+
+        let last = source.rsplit('\n').next().unwrap_or(source);
+        let line = source.matches('\n').count() + 1;
+        let column = last.chars().count() + 1;
+        Some(vec![Smell::from_catalog(
+            path.to_path_buf(),
+            line,
+            SmellCategory::Hygiene,
+            "missing_final_newline",
+            MessageArgs::new(),
+        )
+        .with_span(Span {
+            line,
+            column,
+            end_line: line,
+            end_column: column,
+        })
+        .with_fix(Fix::new(
+            "Add final newline",
+            vec![Edit::insert(source.len(), line_ending(source))],
+        ))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fix;
+    use std::path::PathBuf;
+    use tree_sitter::Parser;
+
+    #[test]
+    fn test_whitespace_fixes_skip_strings() {
+        let code = "x = 1  \r\ns = \"\"\"keep   \r\n\"\"\"\t\r\ny = 2";
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let root = tree.root_node();
+        let path = PathBuf::from("t.py");
+        let config = LintConfig::default();
+
+        let mut smells = TrailingWhitespaceRule
+            .check(root, code, &path, &config)
+            .expect("trailing whitespace");
+        assert_eq!(
+            smells
+                .iter()
+                .map(|s| (s.line, s.column))
+                .collect::<Vec<_>>(),
+            [(1, 6), (3, 4)]
+        );
+        smells.extend(FinalNewlineRule.check(root, code, &path, &config).unwrap());
+        assert!(TrailingWhitespaceRule
+            .check(root.child(0).unwrap(), code, &path, &config)
+            .is_none());
+
+        let fixes: Vec<&Fix> = smells.iter().filter_map(|s| s.fix.as_ref()).collect();
+        let result = fix::apply(code, &fixes);
+        assert_eq!(
+            result.output,
+            "x = 1\r\ns = \"\"\"keep   \r\n\"\"\"\r\ny = 2\r\n"
+        );
+    }
+}
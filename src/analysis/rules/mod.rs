@@ -1,21 +1,20 @@
+use crate::analysis::metrics::Grammar;
 use crate::core::config::LintConfig;
-use crate::core::rules::Smell;
+use crate::core::messages::MessageArgs;
+use crate::core::rules::{Smell, SmellCategory, Span};
 use std::path::Path;
 use tree_sitter::Node;
 
-// Sub-módulos para que sean accesibles desde fuera.
+// Submodules, public so the analyzers can reach them.
+pub mod common;
 pub mod python;
 pub mod rust;
 
-/// El contrato que debe cumplir cualquier regla de análisis individual.
-/// T se refiere al tipo de lenguaje si quisiéramos ser estrictos,
-/// pero tree-sitter usa nodos genéricos, así que simplificamos.
+/// The contract every individual analysis rule fulfils. Tree-sitter nodes are
+/// untyped, so the same trait serves every language.
 pub trait Rule: Send + Sync {
-    /// Nombre único de la regla (para logs o debugging).
-    fn name(&self) -> &str;
-
-    /// Método principal. Recibe un nodo y decide si hay un problema.
-    /// Retorna un Option para eficiencia (la mayoría de nodos no tienen problemas).
+    /// Looks at one node and reports what is wrong with it, if anything.
+    /// Returns an Option because most nodes have no problems.
     fn check(
         &self,
         node: Node,
@@ -24,3 +23,76 @@ pub trait Rule: Send + Sync {
         config: &LintConfig,
     ) -> Option<Vec<Smell>>;
 }
+
+/// `deep_nesting` once per function (or for the top-level code, if `node` is
+/// the root), at the deepest point and with the chain of constructs leading to
+/// it. Nested functions count inside the one containing them, so only the
+/// outermost is measured.
+pub fn deep_nesting(
+    grammar: &Grammar,
+    node: Node,
+    source: &str,
+    path: &Path,
+    limit: usize,
+) -> Option<Vec<Smell>> {
+    let outermost = grammar.is_function(node.kind())
+        && std::iter::successors(node.parent(), |n| n.parent())
+            .all(|ancestor| !grammar.is_function(ancestor.kind()));
+    if !outermost && node.parent().is_some() {
+        return None;
+    }
+    let chain = grammar.deepest_nesting(node);
+    let deepest = *chain.last()?;
+    if chain.len() <= limit {
+        return None;
+    }
+
+    let keywords: Vec<&str> = chain
+        .iter()
+        .map(|construct| grammar.construct_keyword(*construct))
+        .collect();
+    let keyword = deepest.child(0).unwrap_or(deepest);
+    Some(vec![Smell::from_catalog(
+        path.to_path_buf(),
+        keyword.start_position().row + 1,
+        SmellCategory::Complexity,
+        "deep_nesting",
+        MessageArgs::from([
+            ("depth", chain.len().into()),
+            ("chain", keywords.join(" > ").as_str().into()),
+        ]),
+    )
+    .with_span(span_of(keyword, source))
+    .with_metric("nesting_depth", chain.len(), limit)])
+}
+
+/// Converts the position of a node (bytes, base 0) into a `Span` (characters, base 1).
+pub fn span_of(node: Node, source: &str) -> Span {
+    let start = node.start_position();
+    let end = node.end_position();
+    Span {
+        line: start.row + 1,
+        column: char_column(source, node.start_byte(), start.column),
+        end_line: end.row + 1,
+        end_column: char_column(source, node.end_byte(), end.column),
+    }
+}
+
+/// Line ending of the file: `\r\n` if it already uses it, `\n` otherwise.
+/// Fixes that add lines follow it.
+pub fn line_ending(source: &str) -> &'static str {
+    if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Column of `byte` in characters, given that it is `byte_column` bytes after the start of its line.
+fn char_column(source: &str, byte: usize, byte_column: usize) -> usize {
+    let line_start = byte.saturating_sub(byte_column);
+    source
+        .get(line_start..byte)
+        .map_or(byte_column, |prefix| prefix.chars().count())
+        + 1
+}
@@ -1,5 +1,7 @@
-use crate::analysis::rules::Rule;
+use crate::analysis::metrics::{function_lines, function_name, PYTHON};
+use crate::analysis::rules::{span_of, Rule};
 use crate::core::config::LintConfig;
+use crate::core::messages::MessageArgs;
 use crate::core::rules::{Smell, SmellCategory};
 use std::path::Path;
 use tree_sitter::Node;
@@ -7,10 +9,6 @@ use tree_sitter::Node;
 pub struct PythonBloatRule;
 
 impl Rule for PythonBloatRule {
-    fn name(&self) -> &str {
-        "PythonBloatRule"
-    }
-
     fn check(
         &self,
         node: Node,
@@ -21,65 +19,65 @@ impl Rule for PythonBloatRule {
         let kind = node.kind();
         let mut smells = Vec::new();
 
-        if kind == "function_definition" {
+        if PYTHON.is_function(kind) {
             // 1. Function Length
             let start = node.start_position().row;
-            let end = node.end_position().row;
-            let length = end.saturating_sub(start);
+            let length = function_lines(node);
 
             if length > config.thresholds.max_function_lines {
-                let name = node
-                    .child_by_field_name("name")
-                    .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                    .unwrap_or("anonymous");
+                let name = function_name(node, source);
 
                 smells.push(
-                    Smell::new(
+                    Smell::from_catalog(
                         path.to_path_buf(),
                         start + 1,
                         SmellCategory::Bloat,
                         "long_function",
-                        &format!(
-                            "Function '{}' is {} lines long (Limit: {}).",
-                            name, length, config.thresholds.max_function_lines
-                        ),
+                        MessageArgs::from([
+                            ("name", name.into()),
+                            ("lines", length.into()),
+                            ("limit", config.thresholds.max_function_lines.into()),
+                        ]),
                     )
-                    .with_context(format!("Lines: {}", length)),
+                    .with_span(span_of(
+                        node.child_by_field_name("name").unwrap_or(node),
+                        source,
+                    ))
+                    .with_context(format!("Lines: {}", length))
+                    .with_metric(
+                        "lines",
+                        length,
+                        config.thresholds.max_function_lines,
+                    ),
                 );
             }
 
             // 2. Parameter Count
             if let Some(params_node) = node.child_by_field_name("parameters") {
-                let mut param_count = 0;
-                let mut cursor = params_node.walk();
-                for child in params_node.children(&mut cursor) {
-                    let k = child.kind();
-                    if k == "identifier" || k == "typed_parameter" || k == "default_parameter" {
-                        let p_name = child.utf8_text(source.as_bytes()).unwrap_or("?");
-                        if p_name != "self" && p_name != "cls" {
-                            param_count += 1;
-                        }
-                    }
-                }
+                let param_count = PYTHON.param_count(params_node, source);
 
                 if param_count > config.thresholds.max_params {
-                    let name = node
-                        .child_by_field_name("name")
-                        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                        .unwrap_or("anonymous");
+                    let name = function_name(node, source);
 
                     smells.push(
-                        Smell::new(
+                        Smell::from_catalog(
                             path.to_path_buf(),
                             start + 1,
                             SmellCategory::Bloat,
                             "too_many_params",
-                            &format!(
-                                "Function '{}' has {} parameters (Limit: {}).",
-                                name, param_count, config.thresholds.max_params
-                            ),
+                            MessageArgs::from([
+                                ("name", name.into()),
+                                ("params", param_count.into()),
+                                ("limit", config.thresholds.max_params.into()),
+                            ]),
                         )
-                        .with_context(format!("Params: {}", param_count)),
+                        .with_span(span_of(params_node, source))
+                        .with_context(format!("Params: {}", param_count))
+                        .with_metric(
+                            "params",
+                            param_count,
+                            config.thresholds.max_params,
+                        ),
                     );
                 }
             }
@@ -118,12 +116,12 @@ def big_func():
     print(5)
 "#;
         let tree = parse(code);
-        // Buscamos el nodo 'function_definition' (es el primer hijo del root 'module')
+        // The 'function_definition' node is the first child of the 'module' root
         let fn_node = tree.root_node().child(0).unwrap();
 
         let rule = PythonBloatRule;
         let mut config = LintConfig::default();
-        config.thresholds.max_function_lines = 3; // Umbral estricto para test
+        config.thresholds.max_function_lines = 3; // Strict limit for the test
 
         let smells = rule
             .check(fn_node, code, &PathBuf::from("test.py"), &config)
@@ -134,7 +132,7 @@ def big_func():
 
     #[test]
     fn test_too_many_params() {
-        // 'self' no debería contar, así que tenemos a,b,c,d,e (5 params)
+        // 'self' does not count, which leaves a,b,c,d,e (5 params)
         let code = "def complex_func(self, a, b, c, d, e): pass";
         let tree = parse(code);
         let fn_node = tree.root_node().child(0).unwrap();
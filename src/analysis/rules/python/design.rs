@@ -0,0 +1,183 @@
+use crate::analysis::rules::{line_ending, span_of, Rule};
+use crate::core::config::LintConfig;
+use crate::core::fix::{Edit, Fix};
+use crate::core::messages::MessageArgs;
+use crate::core::rules::{Smell, SmellCategory};
+use std::path::Path;
+use tree_sitter::Node;
+
+pub struct PythonDesignRule;
+
+impl Rule for PythonDesignRule {
+    fn check(
+        &self,
+        node: Node,
+        source: &str,
+        path: &Path,
+        _config: &LintConfig,
+    ) -> Option<Vec<Smell>> {
+        if node.kind() != "function_definition" {
+            return None;
+        }
+        let params = node.child_by_field_name("parameters")?;
+        let mut smells = Vec::new();
+
+        // 1. Mutable Default Argument: the value is evaluated once, when the function is defined.
+        let mut cursor = params.walk();
+        for param in params.named_children(&mut cursor) {
+            if param.kind() != "default_parameter" && param.kind() != "typed_default_parameter" {
+                continue;
+            }
+            let (Some(name), Some(value)) = (
+                param.child_by_field_name("name"),
+                param.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            if !is_mutable(value, source) {
+                continue;
+            }
+            let name = name.utf8_text(source.as_bytes()).unwrap_or("");
+
+            let mut smell = Smell::from_catalog(
+                path.to_path_buf(),
+                value.start_position().row + 1,
+                SmellCategory::Design,
+                "mutable_default_argument",
+                MessageArgs::from([("name", name.into())]),
+            )
+            .with_span(span_of(value, source));
+            // An annotated parameter would need `Optional[...]` too: only report it.
+            if param.kind() == "default_parameter" {
+                if let Some(fix) = none_default_fix(node, name, value, source) {
+                    smell = smell.with_fix(fix);
+                }
+            }
+            smells.push(smell);
+        }
+
+        if smells.is_empty() {
+            None
+        } else {
+            Some(smells)
+        }
+    }
+}
+
+/// List/dict/set literals (or their comprehensions) and calls to `list()`, `dict()` or `set()`.
+fn is_mutable(value: Node, source: &str) -> bool {
+    match value.kind() {
+        "list"
+        | "dictionary"
+        | "set"
+        | "list_comprehension"
+        | "dictionary_comprehension"
+        | "set_comprehension" => true,
+        "call" => value
+            .child_by_field_name("function")
+            .and_then(|function| function.utf8_text(source.as_bytes()).ok())
+            .is_some_and(|function| matches!(function, "list" | "dict" | "set")),
+        _ => false,
+    }
+}
+
+/// Replaces the value with `None` and creates the object at the start of the
+/// body (after the docstring). Without a body on its own lines there is no
+/// indentation to give the guard.
+fn none_default_fix(function: Node, name: &str, value: Node, source: &str) -> Option<Fix> {
+    let value_text = value.utf8_text(source.as_bytes()).ok()?;
+    if value_text.contains('\n') {
+        return None;
+    }
+    let body = function.child_by_field_name("body")?;
+    let mut cursor = body.walk();
+    let first = body
+        .named_children(&mut cursor)
+        .filter(|statement| statement.kind() != "comment")
+        .enumerate()
+        .find(|(index, statement)| !(*index == 0 && is_docstring(*statement)))
+        .map(|(_, statement)| statement)?;
+    if first.start_position().row == function.start_position().row {
+        return None;
+    }
+
+    let line_start = first.start_byte() - first.start_position().column;
+    let indent = &source[line_start..first.start_byte()];
+    let def_line = &source[function.start_byte() - function.start_position().column..];
+    let def_indent = &def_line[..def_line.len() - def_line.trim_start_matches([' ', '\t']).len()];
+    if !indent.chars().all(|c| c == ' ' || c == '\t') {
+        return None;
+    }
+    let unit = indent
+        .strip_prefix(def_indent)
+        .filter(|unit| !unit.is_empty())
+        .unwrap_or("    ");
+
+    let nl = line_ending(source);
+    let guard = format!("{indent}if {name} is None:{nl}{indent}{unit}{name} = {value_text}{nl}");
+    Some(Fix::new(
+        "Default to None and build the value inside the function",
+        vec![
+            Edit::replace(value.start_byte(), value.end_byte(), "None"),
+            Edit::insert(line_start, &guard),
+        ],
+    ))
+}
+
+fn is_docstring(statement: Node) -> bool {
+    statement.kind() == "expression_statement"
+        && statement.named_child_count() == 1
+        && statement
+            .named_child(0)
+            .is_some_and(|child| child.kind() == "string")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fix;
+    use std::path::PathBuf;
+    use tree_sitter::Parser;
+
+    fn check(code: &str) -> Vec<Smell> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let function = tree.root_node().named_child(0).unwrap();
+        PythonDesignRule
+            .check(
+                function,
+                code,
+                &PathBuf::from("t.py"),
+                &LintConfig::default(),
+            )
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_mutable_default_fix_adds_guard_after_docstring() {
+        let code = "def add(item, items=[], seen: set = set(), n=0):\n  \"\"\"Doc.\"\"\"\n  items.append(item)\n  return items\n";
+        let smells = check(code);
+        assert_eq!(smells.len(), 2);
+        assert!(
+            smells[1].fix.is_none(),
+            "annotated parameters are not rewritten"
+        );
+
+        let result = fix::apply(code, &[smells[0].fix.as_ref().unwrap()]);
+        assert_eq!(
+            result.output,
+            "def add(item, items=None, seen: set = set(), n=0):\n  \"\"\"Doc.\"\"\"\n  if items is None:\n    items = []\n  items.append(item)\n  return items\n"
+        );
+    }
+
+    #[test]
+    fn test_one_line_body_is_reported_without_fix() {
+        let smells = check("def f(cache={}): return cache\n");
+        assert_eq!(smells[0].rule_id, "mutable_default_argument");
+        assert!(smells[0].fix.is_none());
+        assert!(check("def f(a=None, b=(), c=list): pass\n").is_empty());
+    }
+}
@@ -1,5 +1,6 @@
-use crate::analysis::rules::Rule;
+use crate::analysis::rules::{span_of, Rule};
 use crate::core::config::LintConfig;
+use crate::core::messages::MessageArgs;
 use crate::core::rules::{Smell, SmellCategory};
 use std::path::Path;
 use tree_sitter::Node;
@@ -7,10 +8,6 @@ use tree_sitter::Node;
 pub struct PythonHygieneRule;
 
 impl Rule for PythonHygieneRule {
-    fn name(&self) -> &str {
-        "PythonHygieneRule"
-    }
-
     fn check(
         &self,
         node: Node,
@@ -21,13 +18,14 @@ impl Rule for PythonHygieneRule {
         if node.kind() == "comment" {
             let text = node.utf8_text(source.as_bytes()).unwrap_or("");
             if text.contains("TODO") || text.contains("FIXME") {
-                return Some(vec![Smell::new(
+                return Some(vec![Smell::from_catalog(
                     path.to_path_buf(),
                     node.start_position().row + 1,
                     SmellCategory::Hygiene,
                     "todo_comment",
-                    "Found technical debt anchor (TODO/FIXME).",
-                )]);
+                    MessageArgs::new(),
+                )
+                .with_span(span_of(node, source))]);
             }
         }
         None
@@ -1,16 +1,21 @@
 pub mod bloat;
 pub mod complexity;
+pub mod design;
 pub mod hygiene;
 pub mod naming;
 
+use crate::analysis::rules::common::{FinalNewlineRule, TrailingWhitespaceRule};
 use crate::analysis::rules::Rule;
 
-/// Devuelve todas las reglas activas para Python.
+/// Every rule for Python; `[rules]` in the config decides which ones report.
 pub fn get_rules() -> Vec<Box<dyn Rule>> {
     vec![
         Box::new(bloat::PythonBloatRule),
         Box::new(complexity::PythonComplexityRule),
         Box::new(hygiene::PythonHygieneRule),
         Box::new(naming::PythonNamingRule),
+        Box::new(design::PythonDesignRule),
+        Box::new(TrailingWhitespaceRule),
+        Box::new(FinalNewlineRule),
     ]
 }
@@ -1,5 +1,6 @@
-use crate::analysis::rules::Rule;
+use crate::analysis::rules::{span_of, Rule};
 use crate::core::config::LintConfig;
+use crate::core::messages::MessageArgs;
 use crate::core::rules::{Smell, SmellCategory};
 use std::path::Path;
 use tree_sitter::Node;
@@ -7,10 +8,6 @@ use tree_sitter::Node;
 pub struct PythonNamingRule;
 
 impl Rule for PythonNamingRule {
-    fn name(&self) -> &str {
-        "PythonNamingRule"
-    }
-
     fn check(
         &self,
         node: Node,
@@ -26,13 +23,14 @@ impl Rule for PythonNamingRule {
 
                     // Ignore typical single-letter loop vars or coordinates
                     if name.len() < 3 && !["i", "j", "k", "x", "y", "z", "_"].contains(&name) {
-                        return Some(vec![Smell::new(
+                        return Some(vec![Smell::from_catalog(
                             path.to_path_buf(),
                             node.start_position().row + 1,
                             SmellCategory::Naming,
                             "short_variable",
-                            &format!("Variable '{}' is too short. Use descriptive names.", name),
-                        )]);
+                            MessageArgs::from([("name", name.into())]),
+                        )
+                        .with_span(span_of(left, source))]);
                     }
                 }
             }
@@ -60,11 +58,11 @@ mod tests {
         let code = "d = 50";
         let tree = parse(code);
 
-        // Estructura: module -> expression_statement -> assignment
+        // Structure: module -> expression_statement -> assignment
         let root = tree.root_node();
         let stmt = root.child(0).unwrap();
 
-        // Verificamos si es un wrap (expression_statement) y bajamos un nivel
+        // If it is wrapped in an expression_statement, go down one level
         let assign_node = if stmt.kind() == "expression_statement" {
             stmt.child(0).unwrap()
         } else {
@@ -1,5 +1,7 @@
-use crate::analysis::rules::Rule;
+use crate::analysis::metrics::{function_lines, function_name, RUST};
+use crate::analysis::rules::{span_of, Rule};
 use crate::core::config::LintConfig;
+use crate::core::messages::MessageArgs;
 use crate::core::rules::{Smell, SmellCategory};
 use std::path::Path;
 use tree_sitter::Node;
@@ -7,10 +9,6 @@ use tree_sitter::Node;
 pub struct BloatRule;
 
 impl Rule for BloatRule {
-    fn name(&self) -> &str {
-        "RustBloatRule"
-    }
-
     fn check(
         &self,
         node: Node,
@@ -21,54 +19,64 @@ impl Rule for BloatRule {
         let kind = node.kind();
         let mut smells = Vec::new();
 
-        if kind == "function_item" || kind == "function_definition" {
+        if RUST.is_function(kind) {
             // 1. Long Function
             let start = node.start_position().row;
-            let end = node.end_position().row;
-            let length = end.saturating_sub(start);
+            let length = function_lines(node);
 
             if length > config.thresholds.max_function_lines {
-                let name = node
-                    .child_by_field_name("name")
-                    .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                    .unwrap_or("anonymous");
+                let name = function_name(node, source);
 
                 smells.push(
-                    Smell::new(
+                    Smell::from_catalog(
                         path.to_path_buf(),
                         start + 1,
                         SmellCategory::Bloat,
                         "long_function",
-                        &format!(
-                            "Function '{}' is {} lines long (Limit: {}).",
-                            name, length, config.thresholds.max_function_lines
-                        ),
+                        MessageArgs::from([
+                            ("name", name.into()),
+                            ("lines", length.into()),
+                            ("limit", config.thresholds.max_function_lines.into()),
+                        ]),
                     )
-                    .with_context(format!("Lines: {}", length)),
+                    .with_span(span_of(
+                        node.child_by_field_name("name").unwrap_or(node),
+                        source,
+                    ))
+                    .with_context(format!("Lines: {}", length))
+                    .with_metric(
+                        "lines",
+                        length,
+                        config.thresholds.max_function_lines,
+                    ),
                 );
             }
 
             // 2. Parameter Count
             if let Some(params_node) = node.child_by_field_name("parameters") {
-                let param_count = params_node.named_child_count();
+                let param_count = RUST.param_count(params_node, source);
                 if param_count > config.thresholds.max_params {
-                    let name = node
-                        .child_by_field_name("name")
-                        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
-                        .unwrap_or("anonymous");
+                    let name = function_name(node, source);
 
                     smells.push(
-                        Smell::new(
+                        Smell::from_catalog(
                             path.to_path_buf(),
                             start + 1,
                             SmellCategory::Bloat,
                             "too_many_params",
-                            &format!(
-                                "Function '{}' has {} parameters (Limit: {}).",
-                                name, param_count, config.thresholds.max_params
-                            ),
+                            MessageArgs::from([
+                                ("name", name.into()),
+                                ("params", param_count.into()),
+                                ("limit", config.thresholds.max_params.into()),
+                            ]),
                         )
-                        .with_context(format!("Params: {}", param_count)),
+                        .with_span(span_of(params_node, source))
+                        .with_context(format!("Params: {}", param_count))
+                        .with_metric(
+                            "params",
+                            param_count,
+                            config.thresholds.max_params,
+                        ),
                     );
                 }
             }
@@ -114,7 +122,9 @@ mod tests {
 
         let res = rule.check(fn_node, code, &PathBuf::from("test.rs"), &config);
 
-        assert!(res.is_some());
-        assert_eq!(res.unwrap()[0].rule_id, "long_function");
+        let smells = res.expect("long function detected");
+        assert_eq!(smells[0].rule_id, "long_function");
+        let metric = smells[0].metric.expect("threshold rules carry a metric");
+        assert_eq!((metric.name, metric.value, metric.limit), ("lines", 5, 3));
     }
 }
@@ -1,65 +1,22 @@
-use crate::analysis::rules::Rule;
+use crate::analysis::metrics::RUST;
+use crate::analysis::rules::{deep_nesting, Rule};
 use crate::core::config::LintConfig;
-use crate::core::rules::{Smell, SmellCategory};
+use crate::core::rules::Smell;
 use std::path::Path;
 use tree_sitter::Node;
 
 pub struct ComplexityRule;
 
 impl Rule for ComplexityRule {
-    fn name(&self) -> &str {
-        "RustComplexityRule"
-    }
-
     fn check(
         &self,
         node: Node,
-        _source: &str,
+        source: &str,
         path: &Path,
-        _config: &LintConfig,
+        config: &LintConfig,
     ) -> Option<Vec<Smell>> {
-        let kind = node.kind();
-        if matches!(
-            kind,
-            "if_expression"
-                | "for_expression"
-                | "loop_expression"
-                | "while_expression"
-                | "match_expression"
-        ) {
-            let mut depth = 0;
-            let mut ancestor = node.parent();
-
-            while let Some(p) = ancestor {
-                let p_kind = p.kind();
-                if matches!(
-                    p_kind,
-                    "if_expression"
-                        | "for_expression"
-                        | "loop_expression"
-                        | "while_expression"
-                        | "match_expression"
-                        | "function_item"
-                ) {
-                    depth += 1;
-                }
-                ancestor = p.parent();
-            }
-
-            if depth > 5 {
-                return Some(vec![Smell::new(
-                    path.to_path_buf(),
-                    node.start_position().row + 1,
-                    SmellCategory::Complexity,
-                    "deep_nesting",
-                    &format!(
-                        "Code is deeply nested (Depth: {}). Consider extracting functions.",
-                        depth
-                    ),
-                )]);
-            }
-        }
-        None
+        let limit = config.thresholds.max_nesting_depth.rust;
+        deep_nesting(&RUST, node, source, path, limit)
     }
 }
 
@@ -105,7 +62,7 @@ mod tests {
         let config = LintConfig::default();
         let path = PathBuf::from("test.rs");
 
-        let mut found = false;
+        let mut smells = Vec::new();
 
         // Simple walker for test
         fn walk(
@@ -114,21 +71,41 @@ mod tests {
             code: &str,
             path: &Path,
             config: &LintConfig,
-            found: &mut bool,
+            smells: &mut Vec<Smell>,
         ) {
-            if let Some(_) = rule.check(node, code, path, config) {
-                *found = true;
-            }
+            smells.extend(rule.check(node, code, path, config).unwrap_or_default());
             let mut cursor = node.walk();
             for child in node.children(&mut cursor) {
-                walk(child, rule, code, path, config, found);
+                walk(child, rule, code, path, config, smells);
             }
         }
 
-        walk(root, &rule, code, &path, &config, &mut found);
-        assert!(
-            found,
-            "Should detect nested loop inside match inside if inside loop"
+        walk(root, &rule, code, &path, &config, &mut smells);
+        assert_eq!(
+            smells.len(),
+            1,
+            "one report per function, not per construct"
+        );
+        let metric = smells[0].metric.unwrap();
+        assert_eq!((metric.value, metric.limit), (6, 5));
+        assert_eq!(smells[0].line, 9, "reported at the deepest construct");
+        assert_eq!(
+            smells[0].message_args["chain"].to_string(),
+            "loop > if > match > for > if > while"
         );
+
+        let config = LintConfig {
+            thresholds: crate::core::config::Thresholds {
+                max_nesting_depth: crate::core::config::NestingDepth {
+                    rust: 6,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut smells = Vec::new();
+        walk(root, &rule, code, &path, &config, &mut smells);
+        assert!(smells.is_empty(), "the limit is configurable");
     }
 }
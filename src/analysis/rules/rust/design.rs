@@ -0,0 +1,168 @@
+use crate::analysis::rules::{span_of, Rule};
+use crate::core::config::LintConfig;
+use crate::core::fix::{Edit, Fix};
+use crate::core::messages::MessageArgs;
+use crate::core::rules::{Smell, SmellCategory, Span};
+use std::path::Path;
+use tree_sitter::Node;
+
+pub struct DesignRule;
+
+impl Rule for DesignRule {
+    fn check(
+        &self,
+        node: Node,
+        source: &str,
+        path: &Path,
+        _config: &LintConfig,
+    ) -> Option<Vec<Smell>> {
+        if node.kind() != "call_expression" {
+            return None;
+        }
+
+        // 1. unwrap() in Result: `?` propagates the error instead of panicking.
+        let callee = node.child_by_field_name("function")?;
+        let arguments = node.child_by_field_name("arguments")?;
+        if callee.kind() != "field_expression" || arguments.named_child_count() > 0 {
+            return None;
+        }
+        let method = callee.child_by_field_name("field")?;
+        let receiver = callee.child_by_field_name("value")?;
+        if method.utf8_text(source.as_bytes()).ok()? != "unwrap" || !returns_result(node, source) {
+            return None;
+        }
+
+        let start = span_of(method, source);
+        let end = span_of(node, source);
+        Some(vec![Smell::from_catalog(
+            path.to_path_buf(),
+            start.line,
+            SmellCategory::Design,
+            "unwrap_in_result",
+            MessageArgs::new(),
+        )
+        .with_span(Span {
+            end_line: end.end_line,
+            end_column: end.end_column,
+            ..start
+        })
+        // Without knowing whether the receiver is an Option or a Result, or whether
+        // its error converts into the function's, `?` may not compile: suggest it only.
+        .with_fix(Fix::suggestion(
+            "Replace unwrap() with ?",
+            vec![Edit::replace(receiver.end_byte(), node.end_byte(), "?")],
+        ))])
+    }
+}
+
+/// The function containing `node` declares a `Result` return type. Inside a
+/// closure or an async block, `?` would return from them, not from the function.
+fn returns_result(node: Node, source: &str) -> bool {
+    std::iter::successors(node.parent(), |n| n.parent())
+        .find(|ancestor| {
+            matches!(
+                ancestor.kind(),
+                "function_item" | "closure_expression" | "async_block"
+            )
+        })
+        .filter(|ancestor| ancestor.kind() == "function_item")
+        .and_then(|function| function.child_by_field_name("return_type"))
+        .is_some_and(|ret| is_result(ret, source))
+}
+
+/// `Result`, `Result<T, E>` or a path to one (`io::Result<T>`), judged by the
+/// last segment of the outer type: `Option<Result<T, E>>` or `ResultSet` are not.
+fn is_result(ty: Node, source: &str) -> bool {
+    let name = match ty.kind() {
+        "generic_type" => ty.child_by_field_name("type"),
+        _ => Some(ty),
+    };
+    let name = name.and_then(|name| match name.kind() {
+        "scoped_type_identifier" => name.child_by_field_name("name"),
+        "type_identifier" => Some(name),
+        _ => None,
+    });
+    name.and_then(|name| name.utf8_text(source.as_bytes()).ok()) == Some("Result")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fix;
+    use std::path::PathBuf;
+    use tree_sitter::Parser;
+
+    fn collect<'t>(node: Node<'t>, out: &mut Vec<Node<'t>>) {
+        out.push(node);
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            collect(child, out);
+        }
+    }
+
+    fn smells_in(code: &str) -> usize {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let mut nodes = Vec::new();
+        collect(tree.root_node(), &mut nodes);
+        let config = LintConfig::default();
+        nodes
+            .into_iter()
+            .filter_map(|n| DesignRule.check(n, code, &PathBuf::from("t.rs"), &config))
+            .flatten()
+            .count()
+    }
+
+    #[test]
+    fn test_only_result_return_types_count() {
+        assert_eq!(smells_in("fn f() -> io::Result<()> { g().unwrap(); }"), 1);
+        assert_eq!(smells_in("fn f() -> Result { g().unwrap(); }"), 1);
+        assert_eq!(
+            smells_in("fn f() -> Option<Result<u8, E>> { g().unwrap(); }"),
+            0
+        );
+        assert_eq!(smells_in("fn f() -> ResultSet { g().unwrap(); }"), 0);
+        assert_eq!(smells_in("fn f() -> MyResultish<u8> { g().unwrap(); }"), 0);
+    }
+
+    #[test]
+    fn test_unwrap_in_result_fix() {
+        let code = r#"
+fn load(p: &Path) -> anyhow::Result<String> {
+    let text = fs::read_to_string(p)
+        .unwrap();
+    let f = || parse(&text).unwrap();
+    Ok(text.trim().parse().unwrap_or_default())
+}
+fn main() { run().unwrap(); }
+"#;
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let mut nodes = Vec::new();
+        collect(tree.root_node(), &mut nodes);
+
+        let config = LintConfig::default();
+        let smells: Vec<Smell> = nodes
+            .into_iter()
+            .filter_map(|n| DesignRule.check(n, code, &PathBuf::from("t.rs"), &config))
+            .flatten()
+            .collect();
+        assert_eq!(smells.len(), 1);
+        assert_eq!((smells[0].line, smells[0].column), (4, 10));
+        assert!(
+            smells[0].automatic_fix().is_none(),
+            "--fix never applies it"
+        );
+
+        let result = fix::apply(code, &[smells[0].fix.as_ref().unwrap()]);
+        assert!(result
+            .output
+            .contains("let text = fs::read_to_string(p)?;\n"));
+    }
+}
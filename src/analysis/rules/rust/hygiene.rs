@@ -1,5 +1,6 @@
-use crate::analysis::rules::Rule;
+use crate::analysis::rules::{span_of, Rule};
 use crate::core::config::LintConfig;
+use crate::core::messages::MessageArgs;
 use crate::core::rules::{Smell, SmellCategory};
 use std::path::Path;
 use tree_sitter::Node;
@@ -7,10 +8,6 @@ use tree_sitter::Node;
 pub struct HygieneRule;
 
 impl Rule for HygieneRule {
-    fn name(&self) -> &str {
-        "RustHygieneRule"
-    }
-
     fn check(
         &self,
         node: Node,
@@ -24,13 +21,14 @@ impl Rule for HygieneRule {
             let text = node.utf8_text(source.as_bytes()).unwrap_or("");
 
             if text.contains("TODO") || text.contains("FIXME") {
-                return Some(vec![Smell::new(
+                return Some(vec![Smell::from_catalog(
                     path.to_path_buf(),
                     node.start_position().row + 1,
                     SmellCategory::Hygiene,
                     "todo_comment",
-                    "Found technical debt anchor (TODO/FIXME). Don't ignore it.",
-                )]);
+                    MessageArgs::new(),
+                )
+                .with_span(span_of(node, source))]);
             }
         }
         None
@@ -1,16 +1,21 @@
 pub mod bloat;
 pub mod complexity;
+pub mod design;
 pub mod hygiene;
 pub mod naming;
 
+use crate::analysis::rules::common::{FinalNewlineRule, TrailingWhitespaceRule};
 use crate::analysis::rules::Rule;
 
-/// Devuelve todas las reglas activas para Rust.
+/// Every rule for Rust; `[rules]` in the config decides which ones report.
 pub fn get_rules() -> Vec<Box<dyn Rule>> {
     vec![
         Box::new(bloat::BloatRule),
         Box::new(naming::NamingRule),
         Box::new(complexity::ComplexityRule),
         Box::new(hygiene::HygieneRule),
+        Box::new(design::DesignRule),
+        Box::new(TrailingWhitespaceRule),
+        Box::new(FinalNewlineRule),
     ]
 }
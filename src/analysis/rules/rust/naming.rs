@@ -1,5 +1,6 @@
-use crate::analysis::rules::Rule;
+use crate::analysis::rules::{span_of, Rule};
 use crate::core::config::LintConfig;
+use crate::core::messages::MessageArgs;
 use crate::core::rules::{Smell, SmellCategory};
 use std::path::Path;
 use tree_sitter::Node;
@@ -7,10 +8,6 @@ use tree_sitter::Node;
 pub struct NamingRule;
 
 impl Rule for NamingRule {
-    fn name(&self) -> &str {
-        "RustNamingRule"
-    }
-
     fn check(
         &self,
         node: Node,
@@ -27,13 +24,16 @@ impl Rule for NamingRule {
                     let name = pattern.utf8_text(source.as_bytes()).unwrap_or("?");
 
                     if name.len() < 3 && !is_allowed_short_name(name) && !name.starts_with('_') {
-                        smells.push(Smell::new(
-                            path.to_path_buf(),
-                            node.start_position().row + 1,
-                            SmellCategory::Naming,
-                            "short_variable",
-                            &format!("Variable '{}' is too short. Use descriptive names.", name),
-                        ));
+                        smells.push(
+                            Smell::from_catalog(
+                                path.to_path_buf(),
+                                node.start_position().row + 1,
+                                SmellCategory::Naming,
+                                "short_variable",
+                                MessageArgs::from([("name", name.into())]),
+                            )
+                            .with_span(span_of(pattern, source)),
+                        );
                     }
                 }
             }
@@ -72,8 +72,7 @@ mod tests {
     fn test_short_var() {
         let code = "fn main() { let d = 10; }";
         let tree = parse(code);
-        // let_declaration es hijo del block del function_item
-        // Para simplificar, buscamos en el árbol
+        // let_declaration is a child of the function_item's block
         let mut cursor = tree.walk();
         let root = tree.root_node();
 
@@ -96,5 +95,6 @@ mod tests {
             .check(target_node.unwrap(), code, &PathBuf::from("t.rs"), &config)
             .unwrap();
         assert_eq!(smells[0].rule_id, "short_variable");
+        assert_eq!((smells[0].column, smells[0].end_column), (17, 18));
     }
 }
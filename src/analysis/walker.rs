@@ -1,63 +1,169 @@
-use anyhow::Result;
-use ignore::WalkBuilder;
+use super::languages::{Language, LanguageRegistry};
+use ignore::{WalkBuilder, WalkState};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::SyncSender;
+use std::sync::Mutex;
 use tracing::{debug, warn};
 
-// TODO: In the future, move this to the Configuration module or a Language Registry.
-const SUPPORTED_EXTENSIONS: &[&str] = &[
-    "rs", // Rust
-    "py", // Python
-    "js", "jsx", "ts", "tsx",  // JavaScript / TypeScript
-    "go",   // Go
-    "java", // Java
-    "c", "cpp", "h", "hpp", // C/C++
-    "cs",  // C#
-    "php", // PHP
-    "rb",  // Ruby
-];
-
-/// Scans a directory recursively and returns a list of relevant files.
-pub fn walk_directory(path: &Path) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
+/// A file the walker collected, already tagged with its analyzer language.
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub language: Language,
+}
+
+/// Files skipped because their language is recognized but has no analyzer,
+/// keyed by language name.
+pub type UnsupportedFiles = BTreeMap<&'static str, usize>;
+
+/// Scans the given roots (files or directories) recursively using the parallel
+/// walker and streams every file the language registry can analyze into `sink`
+/// as soon as it is discovered. A full `sink` makes the walker wait.
+///
+/// `threads` bounds the number of walker threads (0 = automatic). When several
+/// roots overlap, each file is only sent once. Returns the per-language count of
+/// source files that were skipped for lack of an analyzer.
+pub fn walk_directory(
+    roots: &[PathBuf],
+    threads: usize,
+    registry: &LanguageRegistry,
+    sink: SyncSender<SourceFile>,
+) -> UnsupportedFiles {
+    let unsupported = Mutex::new(UnsupportedFiles::new());
+    let Some((first, rest)) = roots.split_first() else {
+        return UnsupportedFiles::new();
+    };
 
     // Configure the walker
-    let walker = WalkBuilder::new(path)
-        .standard_filters(true) 
-        .hidden(true) 
-        .follow_links(false) 
-        .build();
-
-    for result in walker {
-        match result {
+    let mut builder = WalkBuilder::new(first);
+    for root in rest {
+        builder.add(root);
+    }
+    let walker = builder
+        .standard_filters(true)
+        .hidden(true)
+        .follow_links(false)
+        .threads(threads)
+        .build_parallel();
+
+    // Only needed when roots can overlap (e.g. `src` and `src/main.rs`).
+    let seen = (roots.len() > 1).then(|| Mutex::new(HashSet::new()));
+
+    walker.run(|| {
+        let sink = sink.clone();
+        let seen = seen.as_ref();
+        let unsupported = &unsupported;
+        Box::new(move |result| match result {
             Ok(entry) => {
-                if is_supported_source_file(entry.path()) {
-                    debug!("Collected file: {:?}", entry.path());
-                    files.push(entry.path().to_path_buf());
+                let path = entry.path();
+                if !path.is_file() || !first_visit(seen, path) {
+                    return WalkState::Continue;
                 }
+
+                match registry.detect(path) {
+                    Some(language) => {
+                        debug!("Collected file ({}): {:?}", language, path);
+                        let file = SourceFile {
+                            path: entry.into_path(),
+                            language,
+                        };
+                        // The receiver only hangs up if analysis was aborted.
+                        if sink.send(file).is_err() {
+                            return WalkState::Quit;
+                        }
+                    }
+                    None => {
+                        if let Some(name) = registry.unsupported_language(path) {
+                            debug!("No analyzer for {} file: {:?}", name, path);
+                            if let Ok(mut counts) = unsupported.lock() {
+                                *counts.entry(name).or_insert(0) += 1;
+                            }
+                        }
+                    }
+                }
+                WalkState::Continue
             }
             Err(err) => {
                 warn!("Skipping entry due to error: {}", err);
+                WalkState::Continue
             }
-        }
-    }
+        })
+    });
 
-    Ok(files)
+    unsupported.into_inner().unwrap_or_default()
 }
 
-/// Helper puro para validar si un archivo debe ser analizado.
-/// Extraído para reducir la complejidad ciclomática y longitud de walk_directory.
-fn is_supported_source_file(path: &Path) -> bool {
-    // 1. Must be a file
-    if !path.is_file() {
-        return false;
+/// Removes duplicated targets (same file or directory spelled differently),
+/// keeping the first spelling. Targets that do not exist are dropped with a warning.
+pub fn dedup_targets(targets: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    targets
+        .into_iter()
+        .filter(|target| match fs::canonicalize(target) {
+            Ok(canonical) => seen.insert(canonical),
+            Err(err) => {
+                warn!("Skipping target {:?}: {}", target, err);
+                false
+            }
+        })
+        .collect()
+}
+
+/// Parses a path list as produced by `git diff --name-only` or `find -print0`:
+/// NUL-separated if the input contains any NUL byte, newline-separated otherwise.
+pub fn parse_path_list(content: &str) -> Vec<PathBuf> {
+    let separator = if content.contains('\0') { '\0' } else { '\n' };
+    content
+        .split(separator)
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Records `path` in the shared set, returning false if it was already walked.
+fn first_visit(seen: Option<&Mutex<HashSet<PathBuf>>>, path: &Path) -> bool {
+    let Some(seen) = seen else {
+        return true;
+    };
+    let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    seen.lock().map(|mut set| set.insert(key)).unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_newline_list() {
+        let paths = parse_path_list("src/main.rs\r\n\nsrc/lib.rs\n");
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("src/main.rs"), PathBuf::from("src/lib.rs")]
+        );
     }
 
-    // 2. Check Extension
-    path.extension()
-        .and_then(|s| s.to_str())
-        .map(|ext| {
-            let ext_lower = ext.to_lowercase();
-            SUPPORTED_EXTENSIONS.contains(&ext_lower.as_str())
-        })
-        .unwrap_or(false)
-}
\ No newline at end of file
+    #[test]
+    fn test_parse_nul_list_keeps_newlines_in_names() {
+        let paths = parse_path_list("a b.py\0weird\nname.py\0");
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("a b.py"), PathBuf::from("weird\nname.py")]
+        );
+    }
+
+    #[test]
+    fn test_dedup_targets() {
+        let targets = vec![
+            PathBuf::from("src"),
+            PathBuf::from("./src"),
+            PathBuf::from("does/not/exist"),
+            PathBuf::from("Cargo.toml"),
+        ];
+        assert_eq!(
+            dedup_targets(targets),
+            vec![PathBuf::from("src"), PathBuf::from("Cargo.toml")]
+        );
+    }
+}
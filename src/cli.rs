@@ -0,0 +1,336 @@
+use crate::analysis::walker::{dedup_targets, parse_path_list};
+use crate::core::config::{LintConfig, DEFAULT_CONFIG_FILE};
+use crate::core::messages::Lang;
+use crate::reporting::console::{GroupBy, SortKey};
+use crate::reporting::terminal::ColorChoice;
+use crate::reporting::{OutputFormat, OutputTarget};
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Baseline written by `lint baseline` unless `--output` says otherwise.
+pub const DEFAULT_BASELINE_FILE: &str = "lint-baseline.json";
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// When to use colors and terminal styling
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Log what the analysis does; repeat (`-dd`) for per-node traces
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub debug: u8,
+
+    /// Without a subcommand, `lint [PATH]...` runs `lint check`
+    #[command(flatten)]
+    pub check: CheckArgs,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Analyze the code and report smells (the default)
+    Check(CheckArgs),
+    /// List every rule with its category and default severity
+    Rules,
+    /// Describe a rule and how to fix what it reports
+    Explain {
+        /// Rule id, as shown in reports (e.g. `deep_nesting`)
+        rule: String,
+    },
+    /// Write a starter `lint.toml` with thresholds that fit the current code
+    Init(InitArgs),
+    /// Record the current smells so that later checks only report new ones
+    Baseline(BaselineArgs),
+    /// Print the effective configuration as TOML
+    Config(ConfigArgs),
+    /// Print the JSON Schema of the `--format json` report
+    Schema,
+}
+
+/// Which files to analyze, and with how many threads.
+#[derive(Args, Debug, Clone)]
+pub struct TargetArgs {
+    /// Files or directories to analyze (defaults to the current directory)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// Same as the positional paths; may be repeated
+    #[arg(short, long = "path", value_name = "PATH")]
+    pub path: Vec<PathBuf>,
+
+    /// Read more paths from FILE (`-` for stdin), newline or NUL separated
+    #[arg(long, value_name = "FILE")]
+    pub files_from: Option<PathBuf>,
+
+    /// Worker threads shared by walking and analysis (0 = one per CPU)
+    #[arg(short, long, default_value_t = 0)]
+    pub jobs: usize,
+}
+
+/// Where the configuration comes from.
+#[derive(Args, Debug, Clone)]
+pub struct ConfigArgs {
+    /// Config file (TOML). Defaults to `lint.toml` in the current directory if present
+    #[arg(short, long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Tighten the configured thresholds to the strict profile where it is stricter
+    #[arg(short, long)]
+    pub strict: bool,
+
+    /// Language of the smell messages (defaults to `lang` in the config, then to LANG)
+    #[arg(long, value_enum)]
+    pub lang: Option<Lang>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CheckArgs {
+    #[command(flatten)]
+    pub targets: TargetArgs,
+
+    #[command(flatten)]
+    pub config: ConfigArgs,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Console)]
+    pub format: OutputFormat,
+
+    /// Shorthand for `--format json`
+    #[arg(long, conflicts_with = "format")]
+    pub json: bool,
+
+    /// Extra report to write, e.g. `sarif=lint.sarif`; without `=FILE` it goes to stdout.
+    /// May be repeated to produce several reports from one run
+    #[arg(short, long = "output", value_name = "FORMAT[=FILE]")]
+    pub output: Vec<OutputTarget>,
+
+    /// Console: split the table into one section per file, rule or category
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub group_by: Option<GroupBy>,
+
+    /// Console: order of the table rows
+    #[arg(long, value_enum, value_name = "KEY", default_value_t = SortKey::File)]
+    pub sort: SortKey,
+
+    /// Console: show only the N files with most smells and the N most complex functions
+    #[arg(long, value_name = "N", conflicts_with = "group_by")]
+    pub top: Option<usize>,
+
+    /// Console: print only the summary
+    #[arg(short, long, conflicts_with_all = ["group_by", "top"])]
+    pub quiet: bool,
+
+    /// Fail the execution with exit code 1 if smells are found (Critical for CI)
+    #[arg(long)]
+    pub fail_on_error: bool,
+
+    /// Read source code from stdin instead of walking paths (for editor integrations)
+    #[arg(long, requires = "stdin_filename", conflicts_with = "files_from")]
+    pub stdin: bool,
+
+    /// Path the stdin buffer belongs to; selects the language and is used in the report
+    #[arg(long, value_name = "PATH", requires = "stdin")]
+    pub stdin_filename: Option<PathBuf>,
+
+    /// Apply the automatic fixes of the smells that have one, rewriting the files
+    #[arg(long, conflicts_with = "stdin")]
+    pub fix: bool,
+
+    /// With --fix: print the fixes as a unified diff instead of writing them
+    #[arg(long, requires = "fix")]
+    pub dry_run: bool,
+
+    /// Only report smells missing from this baseline (overrides `baseline` in the config)
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct InitArgs {
+    #[command(flatten)]
+    pub targets: TargetArgs,
+
+    /// The existing config is the starting point: its language mappings are kept
+    #[command(flatten)]
+    pub config: ConfigArgs,
+
+    /// Where to write the config
+    #[arg(short, long, value_name = "FILE", default_value = DEFAULT_CONFIG_FILE)]
+    pub output: PathBuf,
+
+    /// Overwrite the config (and baseline) if they already exist
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct BaselineArgs {
+    #[command(flatten)]
+    pub targets: TargetArgs,
+
+    #[command(flatten)]
+    pub config: ConfigArgs,
+
+    /// Where to write the baseline
+    #[arg(short, long, value_name = "FILE", default_value = DEFAULT_BASELINE_FILE)]
+    pub output: PathBuf,
+}
+
+impl TargetArgs {
+    /// Merges positional paths, `--path` and `--files-from` into a deduplicated target list.
+    pub fn collect(&self) -> Result<Vec<PathBuf>> {
+        let mut requested: Vec<PathBuf> = self.paths.iter().chain(&self.path).cloned().collect();
+
+        if let Some(list) = &self.files_from {
+            let content = if list.as_os_str() == "-" {
+                let mut buf = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut buf)
+                    .context("Could not read path list from stdin")?;
+                buf
+            } else {
+                std::fs::read_to_string(list)
+                    .with_context(|| format!("Could not read path list {:?}", list))?
+            };
+            requested.extend(parse_path_list(&content));
+        } else if requested.is_empty() {
+            requested.push(PathBuf::from("."));
+        }
+
+        let wanted = requested.len();
+        let targets = dedup_targets(requested);
+        if wanted > 0 && targets.is_empty() {
+            bail!("None of the given paths exist");
+        }
+        Ok(targets)
+    }
+}
+
+impl ConfigArgs {
+    /// Loads `--config` (or `./lint.toml`), then applies the `--strict` profile
+    /// and `--lang` on top. The message language falls back to `LANG`.
+    pub fn load(&self) -> Result<LintConfig> {
+        let mut config = self.read()?;
+        config.lang = Some(config.lang.unwrap_or_else(Lang::from_env));
+        Ok(config)
+    }
+
+    /// Like `load`, but for a file linted from stdin: without `--config`, the
+    /// config is the `lint.toml` nearest to `file`, as if it were on disk.
+    pub fn load_for(&self, file: &Path) -> Result<LintConfig> {
+        let dir = file
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let args = ConfigArgs {
+            config: self.config.clone().or_else(|| LintConfig::find(dir)),
+            ..self.clone()
+        };
+        args.load()
+    }
+
+    /// Like `load`, but `lang` stays unset unless the file or `--lang` set it.
+    pub fn read(&self) -> Result<LintConfig> {
+        LintConfig::read(self.config.as_deref(), self.strict, self.lang)
+    }
+}
+
+impl CheckArgs {
+    fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.format
+        }
+    }
+
+    /// All reports to produce. `--format` fills stdout unless an `--output` already does.
+    pub fn output_targets(&self) -> Result<Vec<OutputTarget>> {
+        let mut targets = self.output.clone();
+        let on_stdout = targets.iter().filter(|t| t.path.is_none()).count();
+        if on_stdout > 1 {
+            bail!("Only one --output may write to stdout");
+        }
+        let mut destinations = HashSet::new();
+        for path in targets.iter().filter_map(|t| t.path.as_ref()) {
+            // `out.json` and `./out.json` are the same file.
+            let destination = std::path::absolute(path).unwrap_or_else(|_| path.clone());
+            if !destinations.insert(destination) {
+                bail!("Two --output targets write to {}", path.display());
+            }
+        }
+        if on_stdout == 0 {
+            targets.insert(
+                0,
+                OutputTarget {
+                    format: self.output_format(),
+                    path: None,
+                },
+            );
+        }
+        Ok(targets)
+    }
+
+    /// Format written to stdout; machine-readable ones keep the logs quiet.
+    pub fn stdout_format(&self) -> OutputFormat {
+        self.output
+            .iter()
+            .find(|t| t.path.is_none())
+            .map_or(self.output_format(), |t| t.format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_bare_invocation_is_check() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from(["lint", "src", "--json"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.check.targets.paths, [PathBuf::from("src")]);
+        assert!(cli.check.json);
+
+        let cli = Cli::try_parse_from(["lint", "check", "src", "--color", "never"]).unwrap();
+        assert!(
+            matches!(cli.command, Some(Command::Check(ref args)) if args.targets.paths.len() == 1)
+        );
+        assert_eq!(cli.color, ColorChoice::Never);
+
+        let cli = Cli::try_parse_from(["lint", "init", "-j", "2", "--strict", "-d"]).unwrap();
+        assert!(
+            matches!(cli.command, Some(Command::Init(ref args)) if args.targets.jobs == 2 && args.config.strict)
+        );
+        assert_eq!(cli.debug, 1);
+
+        let cli = Cli::try_parse_from(["lint", "explain", "deep_nesting"]).unwrap();
+        assert!(
+            matches!(cli.command, Some(Command::Explain { ref rule }) if rule == "deep_nesting")
+        );
+        // A directory named like a subcommand is still reachable through `check`.
+        let cli = Cli::try_parse_from(["lint", "check", "rules"]).unwrap();
+        assert!(
+            matches!(cli.command, Some(Command::Check(ref args)) if args.targets.paths == [PathBuf::from("rules")])
+        );
+    }
+
+    #[test]
+    fn test_output_destinations_are_unique() {
+        let targets = |args: &[&str]| {
+            let cli = Cli::try_parse_from([&["lint"], args].concat()).unwrap();
+            cli.check.output_targets()
+        };
+        assert!(targets(&["-o", "json=a.json", "-o", "sarif=a.sarif"]).is_ok());
+        assert!(targets(&["-o", "json=out", "-o", "sarif=./out"]).is_err());
+        assert!(targets(&["-o", "json", "-o", "sarif"]).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::analysis::engine::{analyze_paths, breakdown, Analysis, PipelineOptions};
+use crate::analysis::languages::LanguageRegistry;
+use crate::cli::{BaselineArgs, ConfigArgs, InitArgs, TargetArgs, DEFAULT_BASELINE_FILE};
+use crate::core::rules::{rule_info, Severity, SmellCategory, RULES};
+use crate::reporting::baseline::Baseline;
+use crate::reporting::AtomicFile;
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::info;
+
+/// `lint rules`: one line per rule, in catalog order. Rules marked `off` only
+/// run when `[rules]` in the config turns them on.
+pub fn rules() -> Result<()> {
+    let width = RULES.iter().map(|rule| rule.id.len()).max().unwrap_or(0);
+    let mut out = std::io::stdout().lock();
+    for rule in RULES {
+        writeln!(
+            out,
+            "{:<width$}  {:<10}  {:<7}  {:<3}  {}",
+            rule.id,
+            rule.category.to_string(),
+            severity(rule.severity),
+            if rule.default_enabled { "on" } else { "off" },
+            rule.summary,
+        )?;
+    }
+    Ok(())
+}
+
+/// `lint explain RULE`: everything the catalog knows about a rule.
+pub fn explain(id: &str) -> Result<()> {
+    let Some(rule) = rule_info(id) else {
+        bail!("Unknown rule '{}'. Run `lint rules` to list them.", id);
+    };
+    let mut out = std::io::stdout().lock();
+    writeln!(
+        out,
+        "{} ({}, {})",
+        rule.id,
+        rule.category,
+        severity(rule.severity)
+    )?;
+    writeln!(out)?;
+    writeln!(out, "{}", rule.summary)?;
+    writeln!(out)?;
+    writeln!(out, "How to fix: {}", rule.help)?;
+    if !rule.default_enabled {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "Off by default; enable it with `{} = true` under [rules] in lint.toml.",
+            rule.id
+        )?;
+    }
+    Ok(())
+}
+
+/// `lint init`: analyzes the code with the current config (the defaults if
+/// there is none, or the strict thresholds), raises the thresholds to fit what
+/// it finds and writes the result as `lint.toml`. The smells no threshold can
+/// silence go to a baseline next to it, so the first `lint check` with the new
+/// config is clean.
+pub fn init(args: &InitArgs) -> Result<()> {
+    let baseline_path = args.output.with_file_name(DEFAULT_BASELINE_FILE);
+    for path in [&args.output, &baseline_path] {
+        if path.exists() && !args.force {
+            bail!("{:?} already exists; use --force to overwrite it", path);
+        }
+    }
+
+    // Regenerating a config starts from the one being replaced.
+    let mut source = args.config.clone();
+    if source.config.is_none() && args.output.is_file() {
+        source.config = Some(args.output.clone());
+    }
+    let mut config = source.read()?;
+    config.baseline = None;
+    let targets = args.targets.collect()?;
+    let before = analyze_paths(&targets, &config, jobs(&args.targets), false)?;
+    let registry = LanguageRegistry::new(&config.languages);
+    config.thresholds.fit(&before.smells, |path| {
+        registry.detect(path).map(|language| language.to_string())
+    });
+
+    // Second pass with the fitted thresholds: whatever is left needs a baseline.
+    let after = analyze_paths(&targets, &config, jobs(&args.targets), true)?;
+    if !after.smells.is_empty() {
+        Baseline::from_smells(&after.smells)
+            .save(&baseline_path)
+            .with_context(|| format!("Could not write {:?}", baseline_path))?;
+        info!(
+            "Recorded {} smells without a threshold in {:?}.",
+            after.smells.len(),
+            baseline_path
+        );
+        // Next to the config, and relative to it.
+        config.baseline = Some(PathBuf::from(DEFAULT_BASELINE_FILE));
+    }
+
+    let toml = format!(
+        "{}\n{}",
+        starter_header(&before),
+        toml::to_string(&config).context("Could not serialize the config")?
+    );
+    let mut file = AtomicFile::create(&args.output)
+        .with_context(|| format!("Could not write {:?}", args.output))?;
+    file.write_all(toml.as_bytes())?;
+    file.commit()
+        .with_context(|| format!("Could not write {:?}", args.output))?;
+    info!("Wrote {:?}; `lint check` now starts green.", args.output);
+    Ok(())
+}
+
+/// Comment block at the top of the generated config: what `init` found.
+fn starter_header(analysis: &Analysis) -> String {
+    let mut by_category: BTreeMap<SmellCategory, usize> = BTreeMap::new();
+    for smell in &analysis.smells {
+        *by_category.entry(smell.category).or_default() += 1;
+    }
+    let languages = breakdown(&analysis.analyzed);
+    let smells = breakdown(&by_category);
+    format!(
+        "# Starter configuration written by `lint init`.\n\
+         # Analyzed {} files{}.\n\
+         # Smells with the initial thresholds: {}{}.\n\
+         # Thresholds were raised to fit the current code; tighten them over time.\n",
+        analysis.files_analyzed(),
+        languages,
+        analysis.smells.len(),
+        smells,
+    )
+}
+
+/// `lint baseline`: records every current smell, so that `check --baseline`
+/// (or `baseline` in the config) only reports new ones.
+pub fn baseline(args: &BaselineArgs) -> Result<()> {
+    let mut config = args.config.load()?;
+    config.baseline = None;
+    let targets = args.targets.collect()?;
+    let analysis = analyze_paths(&targets, &config, jobs(&args.targets), false)?;
+    let baseline = Baseline::from_smells(&analysis.smells);
+    baseline
+        .save(&args.output)
+        .with_context(|| format!("Could not write {:?}", args.output))?;
+    info!("Recorded {} smells in {:?}.", baseline.len(), args.output);
+    Ok(())
+}
+
+/// `lint config`: the configuration `check` would use, as TOML.
+pub fn config(args: &ConfigArgs) -> Result<()> {
+    let config = args.load()?;
+    let toml = toml::to_string(&config).context("Could not serialize the config")?;
+    print!("{}", toml);
+    Ok(())
+}
+
+/// Pipeline options with the `--jobs` of the subcommand.
+fn jobs(args: &TargetArgs) -> PipelineOptions<'static> {
+    PipelineOptions {
+        jobs: args.jobs,
+        ..PipelineOptions::default()
+    }
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starter_header_summarizes_the_run() {
+        let mut analysis = Analysis::default();
+        analysis
+            .analyzed
+            .insert(crate::analysis::languages::Language::Rust, 3);
+        analysis.smells = vec![crate::core::rules::Smell::new(
+            PathBuf::from("a.rs"),
+            1,
+            SmellCategory::Bloat,
+            "long_function",
+            "",
+        )];
+        assert_eq!(
+            starter_header(&analysis),
+            "# Starter configuration written by `lint init`.\n\
+             # Analyzed 3 files (rust: 3).\n\
+             # Smells with the initial thresholds: 1 (BLOAT: 1).\n\
+             # Thresholds were raised to fit the current code; tighten them over time.\n"
+        );
+    }
+}
@@ -1,14 +1,43 @@
+use super::messages::Lang;
+use super::rules::{rule_info, Smell};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Config file picked up automatically when `--config` is not given.
+pub const DEFAULT_CONFIG_FILE: &str = "lint.toml";
 
 /// Global configuration for the lint tool.
 /// Defines thresholds and tolerances for the analysis engine.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LintConfig {
     pub thresholds: Thresholds,
+    pub languages: LanguageConfig,
+    /// Language of the smell messages. `--lang` overrides it; when neither is
+    /// set, it comes from the locale (`LANG`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<Lang>,
+    /// Baseline file (see `lint baseline`); smells recorded in it are not
+    /// reported. A relative path is relative to the config file. `--baseline`
+    /// overrides it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<PathBuf>,
+    /// Rules turned on or off by id, over their default (see `lint rules`).
+    ///
+    /// ```toml
+    /// [rules]
+    /// trailing_whitespace = true
+    /// short_variable = false
+    /// ```
+    pub rules: BTreeMap<String, bool>,
     // Future: exclude_paths, active_rules, etc.
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Thresholds {
     /// Max allowed complexity (Cognitive or Cyclomatic).
     pub max_complexity: u32,
@@ -20,12 +49,41 @@ pub struct Thresholds {
     pub max_file_lines: usize,
     /// Max files in a single directory (flat).
     pub max_dir_files: usize,
+    /// Max nested control-flow constructs per function, per language.
+    pub max_nesting_depth: NestingDepth,
+}
+
+/// Nesting limits per language: idiomatic code nests to different depths, so a
+/// single limit would be too loose for one or too tight for the other.
+///
+/// ```toml
+/// [thresholds.max_nesting_depth]
+/// rust = 5
+/// python = 4
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NestingDepth {
+    pub rust: usize,
+    pub python: usize,
 }
 
-impl Default for LintConfig {
+impl Default for NestingDepth {
     fn default() -> Self {
         Self {
-            thresholds: Thresholds::default(),
+            rust: 5,
+            python: 4, // Python tends to be flatter, so depth > 4 is definitely messy.
+        }
+    }
+}
+
+impl NestingDepth {
+    /// The limit of a language, by the name used in configuration files.
+    fn for_language(&mut self, name: &str) -> Option<&mut usize> {
+        match name {
+            "rust" => Some(&mut self.rust),
+            "python" => Some(&mut self.python),
+            _ => None,
         }
     }
 }
@@ -39,11 +97,130 @@ impl Default for Thresholds {
             max_params: 4,          // More than 4 is usually a Data Clump.
             max_file_lines: 300,    // Single Responsibility Principle limit.
             max_dir_files: 20,      // Prevents "God Packages".
+            max_nesting_depth: NestingDepth::default(),
         }
     }
 }
 
+impl Thresholds {
+    /// Lowers each limit to the one in `limits` when that is stricter.
+    pub fn tighten(&mut self, limits: &Thresholds) {
+        self.max_complexity = self.max_complexity.min(limits.max_complexity);
+        self.max_function_lines = self.max_function_lines.min(limits.max_function_lines);
+        self.max_params = self.max_params.min(limits.max_params);
+        self.max_file_lines = self.max_file_lines.min(limits.max_file_lines);
+        self.max_dir_files = self.max_dir_files.min(limits.max_dir_files);
+        self.max_nesting_depth = NestingDepth {
+            rust: self
+                .max_nesting_depth
+                .rust
+                .min(limits.max_nesting_depth.rust),
+            python: self
+                .max_nesting_depth
+                .python
+                .min(limits.max_nesting_depth.python),
+        };
+    }
+
+    /// Raises each limit to the largest value measured in `smells`, so that the
+    /// code they come from passes with the resulting thresholds. `language_of`
+    /// names the language of a file, for the per-language limits.
+    pub fn fit(&mut self, smells: &[Smell], language_of: impl Fn(&Path) -> Option<String>) {
+        for smell in smells {
+            let Some(metric) = smell.metric else {
+                continue;
+            };
+            match metric.name {
+                "lines" => self.max_function_lines = self.max_function_lines.max(metric.value),
+                "params" => self.max_params = self.max_params.max(metric.value),
+                "nesting_depth" => {
+                    let language = language_of(&smell.file_path);
+                    if let Some(limit) = language
+                        .as_deref()
+                        .and_then(|name| self.max_nesting_depth.for_language(name))
+                    {
+                        *limit = (*limit).max(metric.value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Extra file-to-language mappings on top of the built-in ones.
+///
+/// ```toml
+/// [languages.extensions]
+/// pyx = "python"
+///
+/// [languages.filenames]
+/// BUILD = "python"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageConfig {
+    /// Extension (without dot) -> language name.
+    pub extensions: BTreeMap<String, String>,
+    /// Exact file name -> language name.
+    pub filenames: BTreeMap<String, String>,
+}
+
 impl LintConfig {
+    /// Loads a TOML config file. Missing sections fall back to the defaults.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Could not read config {:?}", path))?;
+        let mut config: Self =
+            toml::from_str(&content).with_context(|| format!("Invalid config {:?}", path))?;
+        if let Some(id) = config.rules.keys().find(|id| rule_info(id).is_none()) {
+            bail!(
+                "Unknown rule '{}' in {:?}. Run `lint rules` to list them.",
+                id,
+                path
+            );
+        }
+        if let (Some(baseline), Some(dir)) = (&config.baseline, path.parent()) {
+            config.baseline = Some(dir.join(baseline));
+        }
+        Ok(config)
+    }
+
+    /// Loads `path`, or `lint.toml` in the current directory if there is one,
+    /// or the defaults. `strict` tightens the thresholds to the strict profile
+    /// where it is stricter; `lang` overrides the file's.
+    pub fn read(path: Option<&Path>, strict: bool, lang: Option<Lang>) -> Result<Self> {
+        let default = Path::new(DEFAULT_CONFIG_FILE);
+        let path = path.or_else(|| Some(default).filter(|p| p.is_file()));
+        let mut config = match path {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+        if strict {
+            config.thresholds.tighten(&Self::strict().thresholds);
+        }
+        config.lang = lang.or(config.lang);
+        Ok(config)
+    }
+
+    /// Whether smells of rule `id` are reported: `[rules]` if it names the rule,
+    /// otherwise the rule's default.
+    pub fn rule_enabled(&self, id: &str) -> bool {
+        self.rules
+            .get(id)
+            .copied()
+            .unwrap_or_else(|| rule_info(id).is_none_or(|rule| rule.default_enabled))
+    }
+
+    /// The `lint.toml` that applies to files in `dir`: the first one found in
+    /// `dir` or its parents. `dir` does not need to exist.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        let dir = std::path::absolute(dir).ok()?;
+        dir.ancestors()
+            .map(|ancestor| ancestor.join(DEFAULT_CONFIG_FILE))
+            .find(|candidate| candidate.is_file())
+    }
+
     /// Returns a strict profile for high-reliability systems.
     pub fn strict() -> Self {
         Self {
@@ -53,11 +230,14 @@ impl LintConfig {
                 max_params: 3,
                 max_file_lines: 200,
                 max_dir_files: 10,
+                max_nesting_depth: NestingDepth { rust: 4, python: 3 },
             },
+            ..Self::default()
         }
     }
 
     /// Returns a lenient profile for legacy codebases.
+    #[allow(dead_code)] // Not exposed through the CLI yet.
     pub fn lenient() -> Self {
         Self {
             thresholds: Thresholds {
@@ -66,7 +246,141 @@ impl LintConfig {
                 max_params: 8,
                 max_file_lines: 500,
                 max_dir_files: 50,
+                max_nesting_depth: NestingDepth { rust: 7, python: 6 },
             },
+            ..Self::default()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::SmellCategory;
+
+    #[test]
+    fn test_partial_toml_keeps_defaults() {
+        let config: LintConfig = toml::from_str(
+            r#"
+            [thresholds]
+            max_params = 6
+
+            [languages.extensions]
+            pyx = "python"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.thresholds.max_params, 6);
+        assert_eq!(config.thresholds.max_function_lines, 40);
+        assert_eq!(config.languages.extensions["pyx"], "python");
+    }
+
+    #[test]
+    fn test_baseline_is_relative_to_the_config_file() {
+        let dir = std::env::temp_dir().join(format!("lint-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("lint.toml");
+        fs::write(&path, "baseline = \"lint-baseline.json\"\n").unwrap();
+
+        let config = LintConfig::from_file(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(config.baseline, Some(dir.join("lint-baseline.json")));
+    }
+
+    #[test]
+    fn test_new_rules_are_off_until_enabled() {
+        let config: LintConfig = toml::from_str(
+            r#"
+            [rules]
+            trailing_whitespace = true
+            short_variable = false
+            "#,
+        )
+        .unwrap();
+        assert!(config.rule_enabled("trailing_whitespace"));
+        assert!(!config.rule_enabled("short_variable"));
+        assert!(
+            !config.rule_enabled("unwrap_in_result"),
+            "new rules start off"
+        );
+        assert!(config.rule_enabled("long_function"));
+
+        let dir = std::env::temp_dir().join(format!("lint-rules-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("lint.toml");
+        fs::write(&path, "[rules]\nno_such_rule = true\n").unwrap();
+        let error = LintConfig::from_file(&path).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(error.to_string().contains("no_such_rule"));
+    }
+
+    #[test]
+    fn test_config_is_found_in_a_parent_directory() {
+        let dir = std::env::temp_dir().join(format!("lint-find-{}", std::process::id()));
+        fs::create_dir_all(dir.join("pkg")).unwrap();
+        fs::write(dir.join("lint.toml"), "").unwrap();
+        fs::write(dir.join("pkg/lint.toml"), "").unwrap();
+
+        let nested = LintConfig::find(&dir.join("pkg/not/created/yet"));
+        let top = LintConfig::find(&dir.join("other"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(nested, Some(dir.join("pkg/lint.toml")), "the nearest wins");
+        assert_eq!(top, Some(dir.join("lint.toml")));
+    }
+
+    #[test]
+    fn test_fit_raises_limits_to_measured_values() {
+        let smell = |file, name, value, limit| {
+            Smell::new(PathBuf::from(file), 1, SmellCategory::Bloat, "x", "")
+                .with_metric(name, value, limit)
+        };
+        let mut thresholds = Thresholds::default();
+        thresholds.fit(
+            &[
+                smell("a.rs", "lines", 85, 40),
+                smell("a.py", "lines", 52, 40),
+                smell("a.rs", "nesting_depth", 7, 5),
+                smell("a.py", "nesting_depth", 6, 4),
+                smell("a.go", "nesting_depth", 9, 4),
+            ],
+            |path| match path.extension()?.to_str()? {
+                "rs" => Some("rust".into()),
+                "py" => Some("python".into()),
+                _ => None,
+            },
+        );
+
+        assert_eq!(thresholds.max_function_lines, 85);
+        assert_eq!(thresholds.max_params, 4, "untouched without smells");
+        assert_eq!(
+            thresholds.max_nesting_depth,
+            NestingDepth { rust: 7, python: 6 },
+            "each language fits its own code"
+        );
+    }
+
+    #[test]
+    fn test_strict_only_tightens_configured_limits() {
+        let mut thresholds: Thresholds = toml::from_str(
+            r#"
+            max_params = 2
+            max_function_lines = 60
+
+            [max_nesting_depth]
+            python = 2
+            "#,
+        )
+        .unwrap();
+        assert_eq!(thresholds.max_nesting_depth.rust, 5, "default for Rust");
+        thresholds.tighten(&LintConfig::strict().thresholds);
+
+        assert_eq!(thresholds.max_params, 2, "already stricter than strict");
+        assert_eq!(thresholds.max_function_lines, 25);
+        assert_eq!(
+            thresholds.max_nesting_depth,
+            NestingDepth { rust: 4, python: 2 },
+            "each language keeps its own limit"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use serde::Serialize;
+
+/// Reemplazo de texto: los bytes `start..end` del archivo original pasan a ser
+/// `replacement`. Con `start == end` es una inserción.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Edit {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+impl Edit {
+    pub fn replace(start: usize, end: usize, replacement: &str) -> Self {
+        Self {
+            start,
+            end,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    pub fn insert(at: usize, text: &str) -> Self {
+        Self::replace(at, at, text)
+    }
+
+    pub fn delete(start: usize, end: usize) -> Self {
+        Self::replace(start, end, "")
+    }
+
+    /// Dos ediciones chocan si reemplazan bytes en común, o si una inserción
+    /// cae dentro del rango que la otra reemplaza. Las inserciones en el mismo
+    /// punto no chocan: se aplican en orden.
+    fn overlaps(&self, other: &Edit) -> bool {
+        let inside = |at: usize, edit: &Edit| edit.start < at && at < edit.end;
+        match (self.start == self.end, other.start == other.end) {
+            (true, true) => false,
+            (true, false) => inside(self.start, other),
+            (false, true) => inside(other.start, self),
+            (false, false) => self.start < other.end && other.start < self.end,
+        }
+    }
+}
+
+/// Si `--fix` puede aplicar una corrección sin que nadie la revise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Applicability {
+    /// Siempre deja código válido con el mismo comportamiento.
+    Automatic,
+    /// Puede no compilar o cambiar el comportamiento: solo se muestra en los
+    /// reportes, `--fix` nunca la aplica.
+    Suggestion,
+}
+
+/// Corrección de un smell. Sus ediciones se aplican todas o ninguna.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    /// Qué hace, en imperativo (ej: "Remove trailing whitespace").
+    pub description: String,
+    pub applicability: Applicability,
+    pub edits: Vec<Edit>,
+}
+
+impl Fix {
+    pub fn new(description: &str, edits: Vec<Edit>) -> Self {
+        Self {
+            description: description.to_string(),
+            applicability: Applicability::Automatic,
+            edits,
+        }
+    }
+
+    pub fn suggestion(description: &str, edits: Vec<Edit>) -> Self {
+        Self {
+            applicability: Applicability::Suggestion,
+            ..Self::new(description, edits)
+        }
+    }
+
+    pub fn is_automatic(&self) -> bool {
+        self.applicability == Applicability::Automatic
+    }
+}
+
+/// Resultado de aplicar un conjunto de correcciones a un archivo.
+#[derive(Debug)]
+pub struct Applied {
+    /// Texto corregido.
+    pub output: String,
+    /// Ediciones aplicadas, ordenadas por posición.
+    pub edits: Vec<Edit>,
+    /// Índices (en la lista recibida) de las correcciones aplicadas.
+    pub applied: Vec<usize>,
+    /// Índices de las que se descartaron por solaparse con otra ya aceptada.
+    pub conflicts: Vec<usize>,
+}
+
+/// Aplica `fixes` sobre `source`. Se aceptan en orden: una corrección que se
+/// solape con otra ya aceptada se descarta entera y queda en `conflicts`, para
+/// que el usuario la revise (o la aplique en una segunda pasada).
+pub fn apply(source: &str, fixes: &[&Fix]) -> Applied {
+    let mut accepted: Vec<Edit> = Vec::new();
+    let mut applied = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for (index, fix) in fixes.iter().enumerate() {
+        let valid = fix.edits.iter().all(|edit| {
+            edit.start <= edit.end
+                && edit.end <= source.len()
+                && source.is_char_boundary(edit.start)
+                && source.is_char_boundary(edit.end)
+        });
+        let clashes = fix.edits.iter().enumerate().any(|(i, edit)| {
+            accepted
+                .iter()
+                .chain(&fix.edits[i + 1..])
+                .any(|other| edit.overlaps(other))
+        });
+        if valid && !clashes {
+            accepted.extend(fix.edits.iter().cloned());
+            applied.push(index);
+        } else {
+            conflicts.push(index);
+        }
+    }
+
+    // Orden estable: las inserciones en un mismo punto conservan su orden de llegada.
+    accepted.sort_by_key(|edit| (edit.start, edit.end));
+    let mut output = String::with_capacity(source.len());
+    let mut cursor = 0;
+    for edit in &accepted {
+        output.push_str(&source[cursor..edit.start]);
+        output.push_str(&edit.replacement);
+        cursor = edit.end;
+    }
+    output.push_str(&source[cursor..]);
+
+    Applied {
+        output,
+        edits: accepted,
+        applied,
+        conflicts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_skips_overlapping_fixes() {
+        let source = "let a = b.unwrap();  \n";
+        let unwrap = Fix::new("Use ?", vec![Edit::replace(9, 18, "?")]);
+        let clash = Fix::new("Rename", vec![Edit::replace(8, 12, "c")]);
+        let trailing = Fix::new("Trim", vec![Edit::delete(19, 21)]);
+        let guard = Fix::new("Guard", vec![Edit::insert(0, "// a\n")]);
+        let guard_too = Fix::new("Guard", vec![Edit::insert(0, "// b\n")]);
+
+        let result = apply(source, &[&unwrap, &clash, &trailing, &guard, &guard_too]);
+        assert_eq!(result.output, "// a\n// b\nlet a = b?;\n");
+        assert_eq!(result.applied, [0, 2, 3, 4]);
+        assert_eq!(result.conflicts, [1]);
+    }
+
+    #[test]
+    fn test_insertion_inside_a_replacement_conflicts() {
+        let source = "abcdef";
+        let replace = Fix::new("Replace", vec![Edit::replace(1, 4, "X")]);
+        let inside = Fix::new("Insert", vec![Edit::insert(2, "Y")]);
+        let at_edge = Fix::new("Insert", vec![Edit::insert(4, "Z")]);
+
+        let result = apply(source, &[&replace, &inside, &at_edge]);
+        assert_eq!(result.output, "aXZef");
+        assert_eq!(result.conflicts, [1]);
+    }
+}
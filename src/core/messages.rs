@@ -0,0 +1,203 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+
+/// Idiomas disponibles para los mensajes de los smells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+}
+
+impl Lang {
+    /// Idioma del entorno, como hace `gettext`: `LC_ALL`, luego `LC_MESSAGES` y
+    /// por último `LANG`. Cualquier locale que no sea español cae en inglés.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Lang::En, |locale| Lang::from_locale(&locale))
+    }
+
+    /// Interpreta un locale POSIX (`es_ES.UTF-8`, `es`, `C`...).
+    fn from_locale(locale: &str) -> Self {
+        let language = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        if language.eq_ignore_ascii_case("es") {
+            Lang::Es
+        } else {
+            Lang::En
+        }
+    }
+}
+
+/// Valor de un parámetro del mensaje. Los números se serializan como números
+/// para que los consumidores puedan formatearlos a su manera.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum MessageArg {
+    Number(usize),
+    Text(String),
+}
+
+impl fmt::Display for MessageArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageArg::Number(n) => write!(f, "{}", n),
+            MessageArg::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+impl From<usize> for MessageArg {
+    fn from(n: usize) -> Self {
+        MessageArg::Number(n)
+    }
+}
+
+impl From<&str> for MessageArg {
+    fn from(s: &str) -> Self {
+        MessageArg::Text(s.to_string())
+    }
+}
+
+/// Parámetros de un mensaje, por nombre.
+pub type MessageArgs = BTreeMap<&'static str, MessageArg>;
+
+/// Una entrada del catálogo. Los `{nombre}` se sustituyen por el parámetro.
+struct Entry {
+    key: &'static str,
+    en: &'static str,
+    es: &'static str,
+}
+
+/// Catálogo de mensajes, con la misma clave que el `rule_id` de la regla.
+const CATALOG: &[Entry] = &[
+    Entry {
+        key: "long_function",
+        en: "Function '{name}' is {lines} lines long (Limit: {limit}).",
+        es: "La función '{name}' ocupa {lines} líneas (Límite: {limit}).",
+    },
+    Entry {
+        key: "too_many_params",
+        en: "Function '{name}' has {params} parameters (Limit: {limit}).",
+        es: "La función '{name}' recibe {params} parámetros (Límite: {limit}).",
+    },
+    Entry {
+        key: "deep_nesting",
+        en: "Code is nested {depth} levels deep ({chain}). Consider extracting functions.",
+        es: "El código se anida {depth} niveles ({chain}). Considera extraer funciones.",
+    },
+    Entry {
+        key: "todo_comment",
+        en: "Found technical debt anchor (TODO/FIXME).",
+        es: "Deuda técnica pendiente (TODO/FIXME).",
+    },
+    Entry {
+        key: "short_variable",
+        en: "Variable '{name}' is too short. Use descriptive names.",
+        es: "El nombre de la variable '{name}' es demasiado corto. Usa nombres descriptivos.",
+    },
+    Entry {
+        key: "trailing_whitespace",
+        en: "Trailing whitespace at the end of the line.",
+        es: "Espacios en blanco al final de la línea.",
+    },
+    Entry {
+        key: "missing_final_newline",
+        en: "File does not end with a newline.",
+        es: "El archivo no termina en salto de línea.",
+    },
+    Entry {
+        key: "mutable_default_argument",
+        en: "Default value of '{name}' is mutable and shared between calls. Use None instead.",
+        es: "El valor por defecto de '{name}' es mutable y se comparte entre llamadas. Usa None.",
+    },
+    Entry {
+        key: "unwrap_in_result",
+        en: "unwrap() in a function returning Result. Propagate the error with '?'.",
+        es: "unwrap() en una función que devuelve Result. Propaga el error con '?'.",
+    },
+];
+
+/// Compone el mensaje `key` en `lang`, o `None` si la clave no está en el catálogo.
+/// Los parámetros que falten se dejan tal cual (`{name}`).
+pub fn render(key: &str, args: &MessageArgs, lang: Lang) -> Option<String> {
+    let entry = CATALOG.iter().find(|entry| entry.key == key)?;
+    let template = match lang {
+        Lang::En => entry.en,
+        Lang::Es => entry.es,
+    };
+
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}') {
+            Some(close) => {
+                let name = &after[..close];
+                match args.get(name) {
+                    Some(value) => out.push_str(&value.to_string()),
+                    None => out.push_str(&rest[open..open + close + 2]),
+                }
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push_str(&rest[open..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_both_languages() {
+        let args = MessageArgs::from([
+            ("name", "parse".into()),
+            ("lines", 80.into()),
+            ("limit", 40.into()),
+        ]);
+        assert_eq!(
+            render("long_function", &args, Lang::En).unwrap(),
+            "Function 'parse' is 80 lines long (Limit: 40)."
+        );
+        assert_eq!(
+            render("long_function", &args, Lang::Es).unwrap(),
+            "La función 'parse' ocupa 80 líneas (Límite: 40)."
+        );
+        assert_eq!(
+            render("short_variable", &MessageArgs::new(), Lang::En).unwrap(),
+            "Variable '{name}' is too short. Use descriptive names.",
+            "missing arguments stay visible"
+        );
+        assert!(render("no_such_rule", &args, Lang::En).is_none());
+
+        for entry in CATALOG {
+            assert!(
+                crate::core::rules::rule_info(entry.key).is_some(),
+                "{} is not a known rule",
+                entry.key
+            );
+        }
+    }
+
+    #[test]
+    fn test_lang_from_locale() {
+        assert_eq!(Lang::from_locale("es_ES.UTF-8"), Lang::Es);
+        assert_eq!(Lang::from_locale("es"), Lang::Es);
+        assert_eq!(Lang::from_locale("en_US.UTF-8"), Lang::En);
+        assert_eq!(Lang::from_locale("C"), Lang::En);
+        assert_eq!(Lang::from_locale("POSIX"), Lang::En);
+    }
+}
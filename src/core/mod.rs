@@ -1,2 +1,4 @@
 pub mod config;
+pub mod fix;
+pub mod messages;
 pub mod rules;
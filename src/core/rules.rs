@@ -1,18 +1,146 @@
+use super::fix::Fix;
+use super::messages::{self, Lang, MessageArgs};
 use serde::Serialize;
 use std::fmt;
 use std::path::PathBuf;
 
 /// Categorías de olores de código alineadas con los requisitos.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub enum SmellCategory {
     Complexity, // Salud Mental (Bucles anidados, ceguera booleana)
     Bloat,      // Obesidad (Métodos largos, archivos gigantes)
     Hygiene,    // Higiene (Bloques vacíos, TODOs)
     Design,     // Diseño/OOP (Acoplamiento)
     Naming,     // Semántica (Nombres cortos o vagos)
+    #[allow(dead_code)] // Reservado para reglas sin categoría asignada.
     Unknown,
 }
 
+/// Gravedad por defecto de una regla (se traduce a niveles SARIF, etc.).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum Severity {
+    Info,
+    Warning,
+}
+
+/// Metadatos estáticos de una regla, para reportes que describen las reglas.
+#[derive(Debug)]
+pub struct RuleInfo {
+    /// Identificador corto, igual al `rule_id` de los smells.
+    pub id: &'static str,
+    pub category: SmellCategory,
+    pub severity: Severity,
+    /// Descripción de una línea.
+    pub summary: &'static str,
+    /// Sugerencia de cómo corregirlo, para los diagnósticos.
+    pub help: &'static str,
+    /// Activa sin configurarla. Las reglas nuevas empiezan desactivadas para no
+    /// añadir hallazgos al CI de quien actualiza; se activan en `[rules]`.
+    pub default_enabled: bool,
+}
+
+/// Catálogo de todas las reglas conocidas, en todos los lenguajes.
+pub const RULES: &[RuleInfo] = &[
+    RuleInfo {
+        id: "long_function",
+        category: SmellCategory::Bloat,
+        severity: Severity::Warning,
+        summary: "Function body exceeds the configured line limit.",
+        help: "Extract cohesive blocks into smaller, well-named functions.",
+        default_enabled: true,
+    },
+    RuleInfo {
+        id: "too_many_params",
+        category: SmellCategory::Bloat,
+        severity: Severity::Warning,
+        summary: "Function takes more parameters than the configured limit.",
+        help: "Group related parameters into a struct or split the function.",
+        default_enabled: true,
+    },
+    RuleInfo {
+        id: "deep_nesting",
+        category: SmellCategory::Complexity,
+        severity: Severity::Warning,
+        summary: "Control flow is nested too deeply.",
+        help: "Use early returns/guard clauses or extract the inner block into a function.",
+        default_enabled: true,
+    },
+    RuleInfo {
+        id: "todo_comment",
+        category: SmellCategory::Hygiene,
+        severity: Severity::Info,
+        summary: "TODO/FIXME comment left in the code.",
+        help: "Resolve it now or track it in the issue tracker and remove the comment.",
+        default_enabled: true,
+    },
+    RuleInfo {
+        id: "short_variable",
+        category: SmellCategory::Naming,
+        severity: Severity::Info,
+        summary: "Variable name is too short to be descriptive.",
+        help: "Name the variable after what it holds.",
+        default_enabled: true,
+    },
+    RuleInfo {
+        id: "trailing_whitespace",
+        category: SmellCategory::Hygiene,
+        severity: Severity::Info,
+        summary: "Line ends with spaces or tabs.",
+        help: "Remove the trailing whitespace (fixable with --fix).",
+        default_enabled: false,
+    },
+    RuleInfo {
+        id: "missing_final_newline",
+        category: SmellCategory::Hygiene,
+        severity: Severity::Info,
+        summary: "File does not end with a newline.",
+        help: "Add a newline after the last line (fixable with --fix).",
+        default_enabled: false,
+    },
+    RuleInfo {
+        id: "mutable_default_argument",
+        category: SmellCategory::Design,
+        severity: Severity::Warning,
+        summary: "Python parameter defaults to a mutable object shared by every call.",
+        help: "Default to None and create the object inside the function (fixable with --fix).",
+        default_enabled: false,
+    },
+    RuleInfo {
+        id: "unwrap_in_result",
+        category: SmellCategory::Design,
+        severity: Severity::Warning,
+        summary: "unwrap() inside a function that already returns Result.",
+        help: "Propagate the error with `?` instead of panicking; check that the error converts into the function's.",
+        default_enabled: false,
+    },
+];
+
+/// Busca los metadatos de una regla por su identificador.
+pub fn rule_info(id: &str) -> Option<&'static RuleInfo> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Rango del código fuente afectado. Líneas y columnas en base 1, contadas en
+/// caracteres; `end_column` es exclusiva.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// Medida que disparó el smell y el límite configurado que superó, para que los
+/// consumidores puedan calcular cuánto se pasa sin interpretar `context`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Metric {
+    /// Qué se midió (ej: "lines", "params", "nesting_depth").
+    pub name: &'static str,
+    pub value: usize,
+    /// Máximo permitido; el smell aparece cuando `value > limit`.
+    pub limit: usize,
+}
+
 /// Representa una violación específica encontrada en el código.
 #[derive(Debug, Clone, Serialize)]
 pub struct Smell {
@@ -20,14 +148,35 @@ pub struct Smell {
     pub file_path: PathBuf,
     /// Número de línea (base 1).
     pub line: usize,
+    /// Columna inicial (base 1, en caracteres).
+    pub column: usize,
+    /// Línea donde termina el rango afectado.
+    pub end_line: usize,
+    /// Columna final (exclusiva). Igual a `column` si solo se conoce el inicio.
+    pub end_column: usize,
+    /// Ruta del símbolo que lo contiene (ej: `crate::engine::run`, `modulo.Clase.metodo`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
     /// Categoría del problema.
     pub category: SmellCategory,
     /// Identificador corto de la regla (ej: "long_function").
     pub rule_id: String,
-    /// Explicación legible para humanos.
+    /// Explicación legible para humanos, en el idioma elegido.
     pub message: String,
+    /// Clave del mensaje en el catálogo, para que los consumidores lo traduzcan.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<&'static str>,
+    /// Parámetros del mensaje del catálogo.
+    #[serde(skip_serializing_if = "MessageArgs::is_empty")]
+    pub message_args: MessageArgs,
     /// Contexto extra (ej: "Found 8 args, limit is 4").
     pub context: Option<String>,
+    /// Medida estructurada, en las reglas basadas en umbrales.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metric: Option<Metric>,
+    /// Corrección, si la regla sabe cómo arreglarlo (ver `--fix`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
 }
 
 impl Smell {
@@ -42,13 +191,87 @@ impl Smell {
         Self {
             file_path,
             line,
+            column: 1,
+            end_line: line,
+            end_column: 1,
+            symbol: None,
             category,
             rule_id: rule_id.to_string(),
             message: message.to_string(),
+            message_key: None,
+            message_args: MessageArgs::new(),
             context: None,
+            metric: None,
+            fix: None,
+        }
+    }
+
+    /// Constructor con un mensaje del catálogo (en inglés hasta que se llame a
+    /// `localize`). La clave es el propio `rule_id`.
+    pub fn from_catalog(
+        file_path: PathBuf,
+        line: usize,
+        category: SmellCategory,
+        rule_id: &'static str,
+        args: MessageArgs,
+    ) -> Self {
+        let message = messages::render(rule_id, &args, Lang::En).unwrap_or_default();
+        let mut smell = Self::new(file_path, line, category, rule_id, &message);
+        smell.message_key = Some(rule_id);
+        smell.message_args = args;
+        smell
+    }
+
+    /// Vuelve a componer el mensaje del catálogo en `lang`.
+    pub fn localize(&mut self, lang: Lang) {
+        if let Some(message) = self.render(lang) {
+            self.message = message;
         }
     }
 
+    fn render(&self, lang: Lang) -> Option<String> {
+        messages::render(self.message_key?, &self.message_args, lang)
+    }
+
+    /// Gravedad según el catálogo de reglas (Warning si la regla no está catalogada).
+    pub fn severity(&self) -> Severity {
+        rule_info(&self.rule_id)
+            .map(|rule| rule.severity)
+            .unwrap_or(Severity::Warning)
+    }
+
+    /// Builder pattern para fijar el rango exacto (sustituye a la línea de `new`).
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.line = span.line;
+        self.column = span.column;
+        self.end_line = span.end_line;
+        self.end_column = span.end_column;
+        self
+    }
+
+    /// Builder pattern para adjuntar la medida que superó el límite.
+    pub fn with_metric(mut self, name: &'static str, value: usize, limit: usize) -> Self {
+        self.metric = Some(Metric { name, value, limit });
+        self
+    }
+
+    /// Builder pattern para fijar el símbolo que lo contiene.
+    pub fn with_symbol(mut self, symbol: &str) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Builder pattern para adjuntar una corrección automática.
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// La corrección que `--fix` aplica; las sugerencias quedan fuera.
+    pub fn automatic_fix(&self) -> Option<&Fix> {
+        self.fix.as_ref().filter(|fix| fix.is_automatic())
+    }
+
     /// Builder pattern para añadir contexto adicional.
     pub fn with_context(mut self, context: String) -> Self {
         self.context = Some(context);
@@ -1,44 +1,45 @@
+use anyhow::{Context, Result};
 use clap::Parser;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
-use tracing::{error, info, Level};
+use tracing::{error, info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 mod analysis;
+mod cli;
+mod commands;
 mod core;
 mod reporting;
 
+use analysis::engine::{analyze_paths, Analysis, PipelineOptions, Stream};
+use cli::{CheckArgs, Cli, Command};
 use core::config::LintConfig;
+use core::fix::{self, Fix};
+use core::rules::Smell;
+use reporting::baseline::Baseline;
+use reporting::console::ConsoleOptions;
+use reporting::ndjson::NdjsonStream;
+use reporting::terminal::{ColorChoice, Terminal};
+use reporting::{OutputFormat, OutputTarget, Report};
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(short, long, default_value = ".")]
-    path: String,
-
-    #[arg(short, long, action = clap::ArgAction::Count)]
-    debug: u8,
-
-    #[arg(short, long)]
-    strict: bool,
-
-    #[arg(long)]
-    json: bool,
-
-    /// Fail the execution with exit code 1 if smells are found (Critical for CI)
-    #[arg(long)]
-    fail_on_error: bool,
-}
-
-// Cambiamos el retorno a ExitCode
+// Returns an ExitCode so that `--fail-on-error` can fail the process.
 fn main() -> ExitCode {
-    let args = Args::parse();
+    let cli = Cli::parse();
+    let command = cli.command.unwrap_or(Command::Check(cli.check));
 
     // 1. Logger Setup
-    let log_level = if args.json { Level::WARN } else { Level::INFO };
+    let log_level = match (cli.debug, &command) {
+        (0, Command::Check(args)) if args.stdout_format().is_machine_readable() => Level::WARN,
+        (0, _) => Level::INFO,
+        (1, _) => Level::DEBUG,
+        _ => Level::TRACE,
+    };
     let subscriber = FmtSubscriber::builder()
         .with_max_level(log_level)
         .with_writer(std::io::stderr)
+        .with_ansi(Terminal::stderr(cli.color).color)
         .finish();
 
     if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
@@ -46,46 +47,317 @@ fn main() -> ExitCode {
         return ExitCode::FAILURE;
     }
 
-    let target_path = Path::new(&args.path);
+    let result = match command {
+        Command::Check(args) => return check(&args, cli.color),
+        Command::Rules => commands::rules(),
+        Command::Explain { rule } => commands::explain(&rule),
+        Command::Init(args) => commands::init(&args),
+        Command::Baseline(args) => commands::baseline(&args),
+        Command::Config(args) => commands::config(&args),
+        Command::Schema => reporting::json::print_schema().context("Could not print schema"),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error!("{:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-    // 2. Load Config
-    let config = if args.strict {
-        LintConfig::strict()
-    } else {
-        LintConfig::default()
+/// `lint check`: analyzes the targets, applies the baseline and `--fix`, and
+/// writes every requested report.
+fn check(args: &CheckArgs, color: ColorChoice) -> ExitCode {
+    let targets = match args.output_targets() {
+        // The diff takes stdout; reports still go to their files.
+        Ok(targets) if args.dry_run => targets.into_iter().filter(|t| t.path.is_some()).collect(),
+        Ok(targets) => targets,
+        Err(e) => {
+            error!("{:#}", e);
+            return ExitCode::FAILURE;
+        }
     };
 
-    if !args.json {
-        info!("Starting lint on: {}", args.path);
+    // 2. Load Config
+    let loaded = match &args.stdin_filename {
+        Some(filename) if args.stdin => args.config.load_for(filename),
+        _ => args.config.load(),
+    };
+    let mut config = match loaded {
+        Ok(config) => config,
+        Err(e) => {
+            error!("{:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.baseline.is_some() {
+        config.baseline = args.baseline.clone();
     }
 
     // 3. Execution
-    match analysis::walk_directory(target_path) {
-        Ok(files) => {
-            if !args.json {
-                info!("Found {} files to analyze.", files.len());
+    // The baseline and the fixes need every smell before anything is reported.
+    let whole_run = args.stdin || args.fix || config.baseline.is_some();
+    let (streams, deferred) = match open_streams(&targets, whole_run) {
+        Ok(split) => split,
+        Err(e) => {
+            error!("{:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let on_file = |smells: &[Smell]| {
+        for (_, stream) in &streams {
+            stream.file_done(smells);
+        }
+    };
+    let options = PipelineOptions {
+        stream: (!streams.is_empty()).then_some(Stream {
+            on_file: &on_file,
+            keep_smells: !deferred.is_empty(),
+        }),
+        metrics: targets.iter().any(|t| {
+            t.format.needs_metrics() || (t.format == OutputFormat::Console && args.top.is_some())
+        }),
+        ..PipelineOptions::default()
+    };
+    let metrics = options.metrics;
+    let mut analysis = match run_analysis(args, &config, options) {
+        Ok(analysis) => analysis,
+        Err(e) => {
+            error!("Analysis failed: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let baseline = match config.baseline.as_deref().map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            error!("{:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(baseline) = &baseline {
+        apply_baseline(&mut analysis, baseline);
+    }
+    if args.fix {
+        match apply_fixes(&analysis, args.dry_run) {
+            Ok(fixed) => reanalyze(&mut analysis, &fixed, &config, baseline.as_ref(), metrics),
+            Err(e) => {
+                error!("Could not apply fixes: {:#}", e);
+                return ExitCode::FAILURE;
             }
+        }
+    }
+    let smell_count = analysis.total_smells;
+
+    // 4. Reporting
+    let mut report_failed = false;
+    for (target, stream) in streams {
+        if let Err(e) = stream.finish(&analysis) {
+            error!("Could not write {} report: {}", target, e);
+            report_failed = true;
+        }
+    }
+    let report = Report {
+        analysis: &analysis,
+        config: &config,
+        terminal: Terminal::stdout(color),
+        console: ConsoleOptions {
+            group_by: args.group_by,
+            sort: args.sort,
+            top: args.top,
+            quiet: args.quiet,
+        },
+    };
+    for target in deferred {
+        if let Err(e) = target.emit(&report) {
+            error!("Could not write {} report: {}", target, e);
+            report_failed = true;
+        }
+    }
+    if report_failed {
+        return ExitCode::FAILURE;
+    }
 
-            let smells = analysis::engine::run_analysis(&files, &config);
-            let smell_count = smells.len();
+    // 5. Exit Strategy
+    if args.fail_on_error && smell_count > 0 {
+        return ExitCode::FAILURE;
+    }
 
-            // 4. Reporting
-            if args.json {
-                reporting::json::print_report(&smells);
-            } else {
-                reporting::console::print_report(&smells);
-            }
+    ExitCode::SUCCESS
+}
 
-            // 5. Exit Strategy
-            if args.fail_on_error && smell_count > 0 {
-                return ExitCode::FAILURE;
-            }
+/// Drops the smells recorded in the baseline, so only new ones are reported.
+fn apply_baseline(analysis: &mut Analysis, baseline: &Baseline) {
+    let known = baseline.suppress(&mut analysis.smells);
+    analysis.total_smells -= known;
+    if known > 0 {
+        info!(
+            "{} smells already in the baseline were not reported.",
+            known
+        );
+    }
+}
 
-            ExitCode::SUCCESS
+/// Applies the automatic fixes attached to the smells, file by file. With `dry_run` the
+/// files stay untouched and the changes are printed as a unified diff;
+/// otherwise the rewritten files are returned with their new content.
+/// Fixes that overlap an earlier one in the same file are skipped with a warning.
+fn apply_fixes(analysis: &Analysis, dry_run: bool) -> Result<BTreeMap<PathBuf, String>> {
+    let mut by_file: BTreeMap<&Path, Vec<usize>> = BTreeMap::new();
+    for (index, smell) in analysis.smells.iter().enumerate() {
+        if smell.automatic_fix().is_some() {
+            by_file.entry(&smell.file_path).or_default().push(index);
         }
-        Err(e) => {
-            error!("Failed to walk directory: {}", e);
-            ExitCode::FAILURE
+    }
+
+    let mut fixed = 0;
+    let mut rewritten = BTreeMap::new();
+    for (path, indices) in by_file {
+        let source =
+            std::fs::read_to_string(path).with_context(|| format!("Could not read {:?}", path))?;
+        let fixes: Vec<&Fix> = indices
+            .iter()
+            .filter_map(|&i| analysis.smells[i].automatic_fix())
+            .collect();
+        let applied = fix::apply(&source, &fixes);
+
+        for &conflict in &applied.conflicts {
+            let smell = &analysis.smells[indices[conflict]];
+            warn!(
+                "{}:{}: skipped fix for {} because it overlaps another fix; run --fix again",
+                path.display(),
+                smell.line,
+                smell.rule_id
+            );
+        }
+        if applied.applied.is_empty() {
+            continue;
+        }
+        fixed += applied.applied.len();
+
+        if dry_run {
+            print!(
+                "{}",
+                reporting::diff::unified(&reporting::report_path(path), &source, &applied.edits)
+            );
+        } else {
+            let permissions = std::fs::metadata(path)?.permissions();
+            let mut file = reporting::AtomicFile::create(path)
+                .with_context(|| format!("Could not write {:?}", path))?;
+            file.write_all(applied.output.as_bytes())?;
+            file.commit()
+                .with_context(|| format!("Could not write {:?}", path))?;
+            std::fs::set_permissions(path, permissions)?;
+        }
+        rewritten.insert(path.to_path_buf(), applied.output);
+    }
+
+    if dry_run {
+        info!("Would apply {} fixes in {} files.", fixed, rewritten.len());
+        return Ok(BTreeMap::new());
+    }
+    info!("Applied {} fixes in {} files.", fixed, rewritten.len());
+    Ok(rewritten)
+}
+
+/// Replaces the smells (and function metrics) of the files `--fix` rewrote
+/// with those of their new content: fixes that add or remove lines move
+/// everything after them, and a fix may leave other smells behind.
+fn reanalyze(
+    analysis: &mut Analysis,
+    rewritten: &BTreeMap<PathBuf, String>,
+    config: &LintConfig,
+    baseline: Option<&Baseline>,
+    metrics: bool,
+) {
+    if rewritten.is_empty() {
+        return;
+    }
+    let before = analysis.smells.len();
+    analysis
+        .smells
+        .retain(|smell| !rewritten.contains_key(&smell.file_path));
+    analysis.total_smells -= before - analysis.smells.len();
+    analysis
+        .functions
+        .retain(|function| !rewritten.contains_key(&function.file_path));
+
+    for (path, code) in rewritten {
+        let Some(mut fresh) = analysis::engine::analyze_source(path, code, config, metrics) else {
+            continue;
+        };
+        if let Some(baseline) = baseline {
+            baseline.suppress(&mut fresh.smells);
+        }
+        analysis.total_smells += fresh.smells.len();
+        analysis.smells.extend(fresh.smells);
+        analysis.functions.extend(fresh.functions);
+    }
+    analysis.smells.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    analysis.functions.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then_with(|| a.line.cmp(&b.line))
+    });
+}
+
+/// NDJSON targets with their open stream, and the targets written at the end.
+type SplitTargets<'a> = (Vec<(&'a OutputTarget, NdjsonStream)>, Vec<&'a OutputTarget>);
+
+/// Opens the NDJSON targets that can stream while the analysis runs. The other
+/// targets (and every target with `--stdin`, which is a single buffer) are
+/// returned to be written once the analysis is done.
+fn open_streams(targets: &[OutputTarget], stdin: bool) -> Result<SplitTargets<'_>> {
+    let mut streams = Vec::new();
+    let mut deferred = Vec::new();
+    for target in targets {
+        if target.format == OutputFormat::Ndjson && !stdin {
+            let stream = NdjsonStream::open(target.path.as_deref())
+                .with_context(|| format!("Could not open {} report", target))?;
+            streams.push((target, stream));
+        } else {
+            deferred.push(target);
+        }
+    }
+    Ok((streams, deferred))
+}
+
+/// Runs the analysis over stdin or the target directory, depending on the flags.
+fn run_analysis(
+    args: &CheckArgs,
+    config: &LintConfig,
+    options: PipelineOptions,
+) -> Result<Analysis> {
+    if args.stdin {
+        // clap guarantees `--stdin-filename` is present alongside `--stdin`.
+        let filename = args.stdin_filename.as_deref().unwrap_or(Path::new("-"));
+        return analyze_stdin(filename, config, options.metrics);
+    }
+
+    let quiet = args.stdout_format().is_machine_readable();
+    let options = PipelineOptions {
+        jobs: args.targets.jobs,
+        ..options
+    };
+    analyze_paths(&args.targets.collect()?, config, options, quiet)
+}
+
+/// Lints the buffer on stdin as if it were `filename`, which does not need to
+/// exist. `config` is the one that applies to `filename` (see `ConfigArgs::load_for`).
+fn analyze_stdin(filename: &Path, config: &LintConfig, metrics: bool) -> Result<Analysis> {
+    let mut code = String::new();
+    std::io::stdin()
+        .read_to_string(&mut code)
+        .context("Could not read source from stdin")?;
+
+    match analysis::engine::analyze_source(filename, &code, config, metrics) {
+        Some(analysis) => Ok(analysis),
+        None => {
+            warn!("No analyzer for {:?}; nothing to check.", filename);
+            Ok(Analysis::default())
         }
     }
 }
@@ -0,0 +1,133 @@
+use super::{fingerprints, write_atomically};
+use crate::core::rules::Smell;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Versión del formato del archivo de baseline.
+const BASELINE_VERSION: u32 = 1;
+
+/// Smells aceptados en un momento dado, por su huella (ver `fingerprints`). Un
+/// `check` con baseline solo reporta los que no estaban, así que un proyecto
+/// puede adoptar el linter sin arreglar antes todo lo existente.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    version: u32,
+    fingerprints: BTreeSet<String>,
+}
+
+impl Baseline {
+    pub fn from_smells(smells: &[Smell]) -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            fingerprints: fingerprints(smells).into_iter().collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Could not read baseline {:?}", path))?;
+        let baseline: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid baseline {:?}", path))?;
+        if baseline.version != BASELINE_VERSION {
+            bail!(
+                "Baseline {:?} has version {}, expected {}",
+                path,
+                baseline.version,
+                BASELINE_VERSION
+            );
+        }
+        Ok(baseline)
+    }
+
+    /// Escribe el baseline de forma atómica, con una huella por línea para que
+    /// los cambios se lean bien en un diff.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomically(path, |out| {
+            serde_json::to_writer_pretty(&mut *out, self)?;
+            writeln!(out)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Quita de `smells` los que ya estaban en el baseline y devuelve cuántos.
+    pub fn suppress(&self, smells: &mut Vec<Smell>) -> usize {
+        let known: Vec<bool> = fingerprints(smells)
+            .iter()
+            .map(|print| self.fingerprints.contains(print))
+            .collect();
+        let mut index = 0;
+        smells.retain(|_| {
+            index += 1;
+            !known[index - 1]
+        });
+        known.iter().filter(|&&known| known).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::messages::MessageArgs;
+    use crate::core::rules::SmellCategory;
+    use std::path::PathBuf;
+
+    fn smell(line: usize, message: &str) -> Smell {
+        Smell::new(
+            PathBuf::from("a.py"),
+            line,
+            SmellCategory::Naming,
+            "short_variable",
+            message,
+        )
+    }
+
+    #[test]
+    fn test_baseline_only_lets_new_smells_through() {
+        let baseline = Baseline::from_smells(&[smell(3, "Variable 'd'")]);
+        let json = serde_json::to_string(&baseline).unwrap();
+        let baseline: Baseline = serde_json::from_str(&json).unwrap();
+
+        let mut smells = vec![
+            smell(5, "Variable 'd'"),
+            smell(9, "Variable 'd'"),
+            smell(7, "Variable 'e'"),
+        ];
+        assert_eq!(baseline.suppress(&mut smells), 1);
+        assert_eq!(
+            smells.iter().map(|s| s.line).collect::<Vec<_>>(),
+            [9, 7],
+            "a second occurrence of a known smell is new"
+        );
+    }
+
+    #[test]
+    fn test_baselined_smell_survives_growth() {
+        let long_function = |line: usize, lines: usize, limit: usize| {
+            Smell::from_catalog(
+                PathBuf::from("a.py"),
+                line,
+                SmellCategory::Bloat,
+                "long_function",
+                MessageArgs::from([
+                    ("name", "f".into()),
+                    ("lines", lines.into()),
+                    ("limit", limit.into()),
+                ]),
+            )
+            .with_symbol("a.f")
+        };
+        let baseline = Baseline::from_smells(&[long_function(3, 60, 50)]);
+
+        let mut smells = vec![long_function(7, 85, 40)];
+        assert_eq!(baseline.suppress(&mut smells), 1);
+        let mut smells = vec![long_function(7, 85, 40).with_symbol("a.g")];
+        assert_eq!(baseline.suppress(&mut smells), 0);
+    }
+}
@@ -0,0 +1,94 @@
+use super::{group_by_file, report_path};
+use super::{xml::escape, Report, Reporter};
+use crate::core::rules::{Severity, Smell};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+/// Versión del formato Checkstyle que entienden los plugins de CI.
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+pub struct CheckstyleReporter;
+
+impl Reporter for CheckstyleReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render(report.smells()).as_bytes())
+    }
+}
+
+/// Un `<file>` por archivo y un `<error>` por smell. La columna es base 1,
+/// como la espera Checkstyle.
+fn render(smells: &[Smell]) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(out, "<checkstyle version=\"{}\">", CHECKSTYLE_VERSION);
+
+    for (path, file_smells) in group_by_file(smells) {
+        let _ = writeln!(out, "  <file name=\"{}\">", escape(&report_path(path)));
+        for smell in file_smells {
+            let _ = writeln!(
+                out,
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"lint.{}\"/>",
+                smell.line,
+                smell.column,
+                severity(smell.severity()),
+                escape(&smell.message),
+                escape(&smell.rule_id)
+            );
+        }
+        out.push_str("  </file>\n");
+    }
+
+    out.push_str("</checkstyle>\n");
+    out
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::{SmellCategory, Span};
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_checkstyle_groups_by_file_and_escapes() {
+        let smells = vec![
+            Smell::new(
+                PathBuf::from("./src/a.rs"),
+                3,
+                SmellCategory::Naming,
+                "short_variable",
+                "Variable 'd' is too short. Use descriptive names.",
+            )
+            .with_span(Span {
+                line: 3,
+                column: 9,
+                end_line: 3,
+                end_column: 10,
+            }),
+            Smell::new(
+                PathBuf::from("./src/a.rs"),
+                9,
+                SmellCategory::Bloat,
+                "too_many_params",
+                "Function 'f<T>' has 5 parameters (Limit: 4).",
+            ),
+        ];
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<checkstyle version="4.3">
+  <file name="src/a.rs">
+    <error line="3" column="9" severity="info" message="Variable &apos;d&apos; is too short. Use descriptive names." source="lint.short_variable"/>
+    <error line="9" column="1" severity="warning" message="Function &apos;f&lt;T&gt;&apos; has 5 parameters (Limit: 4)." source="lint.too_many_params"/>
+  </file>
+</checkstyle>
+"#;
+        assert_eq!(render(&smells), expected);
+    }
+}
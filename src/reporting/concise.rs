@@ -0,0 +1,91 @@
+use super::{report_path, Report, Reporter};
+use crate::core::rules::Smell;
+use std::env;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub struct ConciseReporter;
+
+impl Reporter for ConciseReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        let cwd = env::current_dir().unwrap_or_default();
+        out.write_all(render(report.smells(), &cwd).as_bytes())
+    }
+}
+
+/// Una línea por smell: `path:line:col: [CATEGORY/rule_id] message`, el formato
+/// que entienden quickfix de Vim, compilation-mode de Emacs y `grep`.
+fn render(smells: &[Smell], cwd: &Path) -> String {
+    let mut rows: Vec<(String, &Smell)> = smells
+        .iter()
+        .map(|smell| (report_path(&relative_to(&smell.file_path, cwd)), smell))
+        .collect();
+
+    rows.sort_by(|(path_a, a), (path_b, b)| {
+        path_a
+            .cmp(path_b)
+            .then(a.line.cmp(&b.line))
+            .then(a.column.cmp(&b.column))
+            .then_with(|| a.rule_id.cmp(&b.rule_id))
+    });
+
+    rows.iter()
+        .map(|(path, smell)| {
+            format!(
+                "{}:{}:{}: [{}/{}] {}\n",
+                path,
+                smell.line,
+                smell.column,
+                smell.category,
+                smell.rule_id,
+                smell.message.replace('\n', " ")
+            )
+        })
+        .collect()
+}
+
+/// Rutas absolutas bajo el directorio de invocación se muestran relativas a él.
+fn relative_to(path: &Path, cwd: &Path) -> PathBuf {
+    path.strip_prefix(cwd).unwrap_or(path).to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::{SmellCategory, Span};
+
+    fn smell(path: &str, line: usize, column: usize, rule_id: &str) -> Smell {
+        Smell::new(
+            PathBuf::from(path),
+            line,
+            SmellCategory::Hygiene,
+            rule_id,
+            "Found technical debt anchor (TODO/FIXME).",
+        )
+        .with_span(Span {
+            line,
+            column,
+            end_line: line,
+            end_column: column + 1,
+        })
+    }
+
+    #[test]
+    fn test_concise_lines_are_sorted_and_relative() {
+        let smells = vec![
+            smell("/work/repo/src/b.py", 3, 1, "todo_comment"),
+            smell("./src/a.py", 10, 5, "todo_comment"),
+            smell("src/a.py", 10, 2, "todo_comment"),
+            smell("/elsewhere/c.py", 1, 1, "todo_comment"),
+        ];
+
+        let out = render(&smells, Path::new("/work/repo"));
+        assert_eq!(
+            out,
+            "/elsewhere/c.py:1:1: [HYGIENE/todo_comment] Found technical debt anchor (TODO/FIXME).\n\
+             src/a.py:10:2: [HYGIENE/todo_comment] Found technical debt anchor (TODO/FIXME).\n\
+             src/a.py:10:5: [HYGIENE/todo_comment] Found technical debt anchor (TODO/FIXME).\n\
+             src/b.py:3:1: [HYGIENE/todo_comment] Found technical debt anchor (TODO/FIXME).\n"
+        );
+    }
+}
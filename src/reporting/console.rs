@@ -1,21 +1,94 @@
-use crate::core::rules::{Smell, SmellCategory};
+use super::terminal::Terminal;
+use super::{Report, Reporter};
+use crate::core::rules::{Severity, Smell, SmellCategory};
+use clap::ValueEnum;
 use colored::Colorize;
-use comfy_table::presets::UTF8_FULL;
+use comfy_table::presets::{NOTHING, UTF8_FULL};
 use comfy_table::{Attribute, Cell, Color, ContentArrangement, Table};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
 
-/// Renders the list of smells to stdout.
-pub fn print_report(smells: &[Smell]) {
-    println!("\n{}", "--- lint REPORT ---".bold().underline());
+/// How to group the detail table (`--group-by`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    File,
+    Rule,
+    Category,
+    /// Enclosing function, type or module
+    Symbol,
+}
+
+/// Order of the rows (`--sort`). Ties are broken by location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SortKey {
+    /// By file, then line
+    #[default]
+    File,
+    /// Warnings first
+    Severity,
+    Rule,
+    Category,
+}
+
+/// Console view selected on the command line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsoleOptions {
+    pub group_by: Option<GroupBy>,
+    pub sort: SortKey,
+    /// Show only the N files with most smells and the N most complex functions.
+    pub top: Option<usize>,
+    /// Only the summary.
+    pub quiet: bool,
+}
+
+pub struct ConsoleReporter;
+
+impl Reporter for ConsoleReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        write_report(report, out)
+    }
+}
+
+/// Renders the summary plus the view selected in `report.console`.
+fn write_report(report: &Report, out: &mut dyn Write) -> io::Result<()> {
+    let smells = report.smells();
+    let options = report.console;
+    writeln!(out, "\n{}", "--- lint REPORT ---".bold().underline())?;
 
     if smells.is_empty() {
-        println!(
+        writeln!(
+            out,
             "\n{}",
             "+++ No smells found. Clean architecture +++".green().bold()
-        );
-        return;
+        )?;
+        return Ok(());
     }
 
-    // 1. Summary by Category
+    write_summary(smells, out)?;
+    if options.quiet {
+        return Ok(());
+    }
+    if let Some(limit) = options.top {
+        return write_top(report, limit, out);
+    }
+
+    let sorted = sort_smells(smells, options.sort);
+    match options.group_by {
+        None => writeln!(out, "{}", smell_table(&sorted, report.terminal)),
+        Some(group_by) => {
+            for (key, group) in group_smells(&sorted, group_by) {
+                let heading = format!("{} ({})", key, group.len());
+                writeln!(out, "{}", heading.bold())?;
+                writeln!(out, "{}\n", smell_table(&group, report.terminal))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_summary(smells: &[Smell], out: &mut dyn Write) -> io::Result<()> {
     let mut complexity = 0;
     let mut bloat = 0;
     let mut hygiene = 0;
@@ -33,22 +106,66 @@ pub fn print_report(smells: &[Smell]) {
         }
     }
 
-    println!("\n📊 Summary:");
-    println!("  • Complexity: {}", complexity.to_string().red());
-    println!("  • Bloat:      {}", bloat.to_string().yellow());
-    println!("  • Hygiene:    {}", hygiene.to_string().blue());
-    println!("  • Naming:     {}", naming.to_string().cyan());
-    println!("  • Design:     {}", design.to_string().magenta());
-    println!("  • Total:      {}\n", smells.len().to_string().bold());
+    writeln!(out, "\n📊 Summary:")?;
+    writeln!(out, "  • Complexity: {}", complexity.to_string().red())?;
+    writeln!(out, "  • Bloat:      {}", bloat.to_string().yellow())?;
+    writeln!(out, "  • Hygiene:    {}", hygiene.to_string().blue())?;
+    writeln!(out, "  • Naming:     {}", naming.to_string().cyan())?;
+    writeln!(out, "  • Design:     {}", design.to_string().magenta())?;
+    let fixable = smells
+        .iter()
+        .filter(|s| s.automatic_fix().is_some())
+        .count();
+    if fixable > 0 {
+        writeln!(
+            out,
+            "  • Fixable:    {} (run with --fix)",
+            fixable.to_string().green()
+        )?;
+    }
+    writeln!(out, "  • Total:      {}\n", smells.len().to_string().bold())
+}
+
+fn sort_smells(smells: &[Smell], key: SortKey) -> Vec<&Smell> {
+    let mut sorted: Vec<&Smell> = smells.iter().collect();
+    let location = |s: &Smell| (s.file_path.clone(), s.line, s.column);
+    match key {
+        SortKey::File => sorted.sort_by_key(|s| location(s)),
+        SortKey::Severity => sorted.sort_by_key(|s| (Reverse(s.severity()), location(s))),
+        SortKey::Rule => sorted.sort_by_key(|s| (s.rule_id.clone(), location(s))),
+        SortKey::Category => sorted.sort_by_key(|s| (s.category, location(s))),
+    }
+    sorted
+}
+
+/// Groups from largest to smallest, keeping the order of `smells` within each.
+fn group_smells<'a>(smells: &[&'a Smell], group_by: GroupBy) -> Vec<(String, Vec<&'a Smell>)> {
+    let mut groups: BTreeMap<String, Vec<&Smell>> = BTreeMap::new();
+    for smell in smells {
+        let key = match group_by {
+            GroupBy::File => smell.file_path.display().to_string(),
+            GroupBy::Rule => smell.rule_id.clone(),
+            GroupBy::Category => smell.category.to_string(),
+            GroupBy::Symbol => smell
+                .symbol
+                .clone()
+                .unwrap_or_else(|| smell.file_path.display().to_string()),
+        };
+        groups.entry(key).or_default().push(smell);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(_, group)| Reverse(group.len()));
+    groups
+}
 
-    // 2. Detailed Table
+fn smell_table(smells: &[&Smell], terminal: Terminal) -> Table {
     let mut table = Table::new();
-    table.load_preset(UTF8_FULL);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    configure_table(&mut table, terminal);
     table.set_header(vec![
         Cell::new("Category").add_attribute(Attribute::Bold),
         Cell::new("File").add_attribute(Attribute::Bold),
         Cell::new("Line").add_attribute(Attribute::Bold),
+        Cell::new("Symbol").add_attribute(Attribute::Bold),
         Cell::new("Issue").add_attribute(Attribute::Bold),
     ]);
 
@@ -66,9 +183,223 @@ pub fn print_report(smells: &[Smell]) {
             Cell::new(format!("{}", smell.category)).fg(category_color),
             Cell::new(smell.file_path.display()).fg(Color::White),
             Cell::new(smell.line).fg(Color::DarkGrey),
+            Cell::new(smell.symbol.as_deref().unwrap_or_default()),
             Cell::new(&smell.message),
         ]);
     }
+    table
+}
+
+/// The files with most smells and the functions with the highest complexity.
+fn write_top(report: &Report, limit: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut files: BTreeMap<&Path, (usize, usize)> = BTreeMap::new();
+    for smell in report.smells() {
+        let (total, warnings) = files.entry(&smell.file_path).or_default();
+        *total += 1;
+        if smell.severity() == Severity::Warning {
+            *warnings += 1;
+        }
+    }
+    let mut files: Vec<_> = files.into_iter().collect();
+    files.sort_by_key(|(_, (total, warnings))| Reverse((*total, *warnings)));
+
+    let mut table = Table::new();
+    configure_table(&mut table, report.terminal);
+    table.set_header(header(&["File", "Smells", "Warnings"]));
+    for (path, (total, warnings)) in files.into_iter().take(limit) {
+        table.add_row(vec![
+            Cell::new(path.display()),
+            Cell::new(total),
+            Cell::new(warnings),
+        ]);
+    }
+    writeln!(out, "{}", format!("Top {} files by smells", limit).bold())?;
+    writeln!(out, "{}\n", table)?;
+
+    let mut functions: Vec<_> = report.analysis.functions.iter().collect();
+    functions.sort_by_key(|f| Reverse((f.complexity, f.nesting, f.lines)));
 
-    println!("{}", table);
+    let mut table = Table::new();
+    configure_table(&mut table, report.terminal);
+    table.set_header(header(&[
+        "Function",
+        "Location",
+        "Complexity",
+        "Nesting",
+        "Lines",
+    ]));
+    for function in functions.into_iter().take(limit) {
+        table.add_row(vec![
+            Cell::new(&function.name),
+            Cell::new(format!(
+                "{}:{}",
+                function.file_path.display(),
+                function.line
+            )),
+            Cell::new(function.complexity),
+            Cell::new(function.nesting),
+            Cell::new(function.lines),
+        ]);
+    }
+    writeln!(
+        out,
+        "{}",
+        format!("Top {} functions by complexity", limit).bold()
+    )?;
+    writeln!(out, "{}", table)
+}
+
+fn header(names: &[&str]) -> Vec<Cell> {
+    names
+        .iter()
+        .map(|name| Cell::new(name).add_attribute(Attribute::Bold))
+        .collect()
+}
+
+/// Below this width the bordered table becomes unreadable.
+const MIN_TABLE_WIDTH: u16 = 60;
+
+/// On a terminal, a bordered table fitted to its width; elsewhere (or when the
+/// width is unknown or too narrow), aligned columns without borders or line
+/// wrapping, easy to read in logs and to grep.
+fn configure_table(table: &mut Table, terminal: Terminal) {
+    // Read the width before `force_no_tty`, which disables its detection.
+    let usable = |width: &u16| *width >= MIN_TABLE_WIDTH;
+    let width = terminal
+        .interactive
+        .then(|| {
+            table
+                .width()
+                .filter(usable)
+                .or_else(|| columns_from_env().filter(usable))
+        })
+        .flatten();
+
+    match width {
+        Some(width) => {
+            table.load_preset(UTF8_FULL);
+            table.set_content_arrangement(ContentArrangement::Dynamic);
+            table.set_width(width);
+        }
+        None => {
+            table.load_preset(NOTHING);
+            table.set_content_arrangement(ContentArrangement::Disabled);
+        }
+    }
+
+    // `--color` decides on color, not comfy-table.
+    table.force_no_tty();
+    if terminal.color {
+        table.enforce_styling();
+    }
+}
+
+fn columns_from_env() -> Option<u16> {
+    std::env::var("COLUMNS").ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::engine::Analysis;
+    use crate::analysis::metrics::FunctionMetrics;
+    use crate::core::config::LintConfig;
+    use std::path::PathBuf;
+
+    fn smell(path: &str, line: usize, category: SmellCategory, rule: &str) -> Smell {
+        Smell::new(PathBuf::from(path), line, category, rule, "msg")
+    }
+
+    fn render(analysis: &Analysis, console: ConsoleOptions) -> String {
+        let config = LintConfig::default();
+        let report = Report {
+            analysis,
+            config: &config,
+            terminal: Terminal::PLAIN,
+            console,
+        };
+        let mut out = Vec::new();
+        write_report(&report, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn sample() -> Analysis {
+        Analysis {
+            smells: vec![
+                smell("a.py", 9, SmellCategory::Naming, "short_variable"),
+                smell("b.py", 2, SmellCategory::Bloat, "long_function"),
+                smell("b.py", 5, SmellCategory::Hygiene, "todo_comment"),
+            ],
+            functions: vec![
+                FunctionMetrics {
+                    file_path: PathBuf::from("a.py"),
+                    name: "simple".to_string(),
+                    line: 1,
+                    lines: 3,
+                    params: 0,
+                    nesting: 0,
+                    complexity: 1,
+                },
+                FunctionMetrics {
+                    file_path: PathBuf::from("b.py"),
+                    name: "tangled".to_string(),
+                    line: 2,
+                    lines: 60,
+                    params: 2,
+                    nesting: 4,
+                    complexity: 12,
+                },
+            ],
+            ..Analysis::default()
+        }
+    }
+
+    #[test]
+    fn test_sort_and_group() {
+        let analysis = sample();
+        let by_severity = sort_smells(&analysis.smells, SortKey::Severity);
+        assert_eq!(by_severity[0].rule_id, "long_function", "warnings first");
+        assert_eq!(by_severity[1].file_path, PathBuf::from("a.py"));
+
+        let sorted = sort_smells(&analysis.smells, SortKey::File);
+        let groups = group_smells(&sorted, GroupBy::File);
+        assert_eq!(groups[0].0, "b.py", "largest group first");
+        assert_eq!(groups[0].1.len(), 2);
+
+        let text = render(
+            &analysis,
+            ConsoleOptions {
+                group_by: Some(GroupBy::Category),
+                ..ConsoleOptions::default()
+            },
+        );
+        assert!(text.contains("NAMING (1)"));
+    }
+
+    #[test]
+    fn test_quiet_and_top_views() {
+        let analysis = sample();
+        let quiet = render(
+            &analysis,
+            ConsoleOptions {
+                quiet: true,
+                ..ConsoleOptions::default()
+            },
+        );
+        assert!(quiet.contains("Total:      3"));
+        assert!(!quiet.contains("b.py"));
+
+        let top = render(
+            &analysis,
+            ConsoleOptions {
+                top: Some(1),
+                ..ConsoleOptions::default()
+            },
+        );
+        assert!(top.contains("Top 1 files by smells"));
+        assert!(top.contains("b.py"));
+        assert!(!top.contains("a.py"), "only the worst file and function");
+        assert!(top.contains("tangled"));
+        assert!(!top.contains("simple"));
+    }
 }
@@ -0,0 +1,125 @@
+use super::{report_path, Report, Reporter};
+use crate::analysis::metrics::FunctionMetrics;
+use crate::core::rules::{Severity, Smell};
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+const SMELL_HEADER: &str = "file,line,column,category,rule,severity,message,context,symbol";
+const METRICS_HEADER: &str = "file,line,name,lines,params,nesting,complexity";
+
+pub struct CsvReporter;
+
+impl Reporter for CsvReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render_smells(report.smells()).as_bytes())
+    }
+}
+
+pub struct MetricsCsvReporter;
+
+impl Reporter for MetricsCsvReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render_metrics(&report.analysis.functions).as_bytes())
+    }
+}
+
+/// Una fila por smell (RFC 4180, con fin de línea CRLF como espera Excel).
+fn render_smells(smells: &[Smell]) -> String {
+    let mut out = format!("{}\r\n", SMELL_HEADER);
+    for smell in smells {
+        let severity = match smell.severity() {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+        };
+        let _ = write!(
+            out,
+            "{},{},{},{},{},{},{},{},{}\r\n",
+            field(&report_path(&smell.file_path)),
+            smell.line,
+            smell.column,
+            smell.category,
+            field(&smell.rule_id),
+            severity,
+            field(&smell.message),
+            field(smell.context.as_deref().unwrap_or_default()),
+            field(smell.symbol.as_deref().unwrap_or_default()),
+        );
+    }
+    out
+}
+
+/// Una fila por función analizada.
+fn render_metrics(functions: &[FunctionMetrics]) -> String {
+    let mut out = format!("{}\r\n", METRICS_HEADER);
+    for function in functions {
+        let _ = write!(
+            out,
+            "{},{},{},{},{},{},{}\r\n",
+            field(&report_path(&function.file_path)),
+            function.line,
+            field(&function.name),
+            function.lines,
+            function.params,
+            function.nesting,
+            function.complexity,
+        );
+    }
+    out
+}
+
+/// Entrecomilla el campo si contiene separadores, comillas o saltos de línea.
+fn field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::SmellCategory;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_smells_csv_quotes_fields() {
+        let smells = vec![Smell::new(
+            PathBuf::from("./src/a, b.py"),
+            3,
+            SmellCategory::Bloat,
+            "too_many_params",
+            "Function 'f' has 5 parameters (Limit: 4).",
+        )
+        .with_context("He said \"too many\"".to_string())
+        .with_symbol("a, b.f")];
+
+        let csv = render_smells(&smells);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], SMELL_HEADER);
+        assert_eq!(
+            lines[1],
+            "\"src/a, b.py\",3,1,BLOAT,too_many_params,warning,\
+             Function 'f' has 5 parameters (Limit: 4).,\"He said \"\"too many\"\"\",\"a, b.f\""
+        );
+        assert_eq!(lines[2], "", "rows end with CRLF");
+    }
+
+    #[test]
+    fn test_metrics_csv_row() {
+        let functions = vec![FunctionMetrics {
+            file_path: PathBuf::from("src/main.rs"),
+            name: "main".to_string(),
+            line: 10,
+            lines: 42,
+            params: 0,
+            nesting: 3,
+            complexity: 7,
+        }];
+        assert_eq!(
+            render_metrics(&functions),
+            format!("{}\r\nsrc/main.rs,10,main,42,0,3,7\r\n", METRICS_HEADER)
+        );
+    }
+}
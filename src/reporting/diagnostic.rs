@@ -0,0 +1,218 @@
+use super::{group_by_file, read_source, Report, Reporter};
+use crate::core::rules::{rule_info, Severity, Smell};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Líneas de contexto alrededor de la línea señalada.
+const CONTEXT_LINES: usize = 1;
+
+pub struct DiagnosticReporter;
+
+impl Reporter for DiagnosticReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render(report.smells(), report.sources()).as_bytes())
+    }
+}
+
+/// Muestra cada smell como rustc/ruff: cabecera, ubicación, el código de
+/// alrededor con el rango señalado subrayado y luego las notas de contexto y
+/// ayuda. El código sale de `sources` si se analizó desde memoria.
+fn render(smells: &[Smell], sources: &BTreeMap<PathBuf, String>) -> String {
+    let mut out = String::new();
+
+    for (path, file_smells) in group_by_file(smells) {
+        let source = read_source(sources, path).unwrap_or_default();
+        let lines: Vec<&str> = source.lines().collect();
+        for smell in file_smells {
+            render_smell(&mut out, smell, &lines);
+        }
+    }
+
+    let warnings = smells
+        .iter()
+        .filter(|s| s.severity() == Severity::Warning)
+        .count();
+    let _ = writeln!(
+        out,
+        "{}",
+        format!(
+            "Found {} smells ({} warnings, {} infos).",
+            smells.len(),
+            warnings,
+            smells.len() - warnings
+        )
+        .bold()
+    );
+    out
+}
+
+fn render_smell(out: &mut String, smell: &Smell, lines: &[&str]) {
+    let label = match smell.severity() {
+        Severity::Warning => "warning".yellow().bold(),
+        Severity::Info => "info".cyan().bold(),
+    };
+    let _ = writeln!(
+        out,
+        "{}{} {}",
+        label,
+        format!("[{}]:", smell.rule_id).bold(),
+        smell.message.bold()
+    );
+
+    let first = smell.line.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (smell.line + CONTEXT_LINES).min(lines.len());
+    let width = last.max(smell.line).to_string().len();
+    let gutter = |prefix: &str| format!("{:>width$} |", prefix, width = width).blue().bold();
+
+    let _ = writeln!(
+        out,
+        "{}{} {}:{}:{}",
+        " ".repeat(width),
+        "-->".blue().bold(),
+        smell.file_path.display(),
+        smell.line,
+        smell.column
+    );
+    let _ = writeln!(out, "{}", gutter(""));
+
+    for number in first..=last {
+        let text = lines[number - 1];
+        let _ = writeln!(out, "{} {}", gutter(&number.to_string()), text);
+        if number == smell.line {
+            let _ = writeln!(out, "{} {}", gutter(""), underline(smell, text));
+        }
+    }
+
+    let _ = writeln!(out, "{}", gutter(""));
+    if let Some(symbol) = &smell.symbol {
+        let _ = writeln!(out, "{} {} {}", " ".repeat(width), "= in:".bold(), symbol);
+    }
+    if let Some(context) = &smell.context {
+        let _ = writeln!(
+            out,
+            "{} {} {}",
+            " ".repeat(width),
+            "= note:".bold(),
+            context
+        );
+    }
+    if let Some(info) = rule_info(&smell.rule_id) {
+        let _ = writeln!(
+            out,
+            "{} {} {}",
+            " ".repeat(width),
+            "= help:".bold(),
+            info.help
+        );
+    }
+    out.push('\n');
+}
+
+/// `^^^` bajo el rango del smell. Si continúa en otras líneas, se subraya
+/// hasta el final de la primera; si el rango está vacío, un solo carácter.
+fn underline(smell: &Smell, text: &str) -> String {
+    let start = smell.column.saturating_sub(1);
+    let end = if smell.end_line == smell.line {
+        smell.end_column.saturating_sub(1)
+    } else {
+        text.chars().count()
+    };
+
+    // Conservar tabulaciones para que el subrayado quede alineado.
+    let padding: String = text
+        .chars()
+        .take(start)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let carets = "^".repeat(end.saturating_sub(start).max(1));
+    format!("{}{}", padding, carets.yellow().bold())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::{SmellCategory, Span};
+
+    /// Quita secuencias ANSI para comparar el texto independientemente del color.
+    fn strip_ansi(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\u{1b}' {
+                chars.by_ref().take_while(|c| *c != 'm').for_each(drop);
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_snippet_underlines_span() {
+        let dir = std::env::temp_dir().join(format!("lint-diag-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sample.py");
+        std::fs::write(&path, "def f():\n    x = compute()\n    return x\n").unwrap();
+
+        let smell = Smell::new(
+            path.clone(),
+            2,
+            SmellCategory::Naming,
+            "short_variable",
+            "Variable 'x' is too short. Use descriptive names.",
+        )
+        .with_span(Span {
+            line: 2,
+            column: 5,
+            end_line: 2,
+            end_column: 6,
+        });
+
+        let text = strip_ansi(&render(&[smell], &BTreeMap::new()));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let expected = format!(
+            "\
+info[short_variable]: Variable 'x' is too short. Use descriptive names.
+ --> {}:2:5
+  |
+1 | def f():
+2 |     x = compute()
+  |     ^
+3 |     return x
+  |
+  = help: Name the variable after what it holds.
+",
+            path.display()
+        );
+        assert!(text.starts_with(&expected), "{}", text);
+        assert!(text.ends_with("Found 1 smells (0 warnings, 1 infos).\n"));
+    }
+
+    #[test]
+    fn test_missing_source_still_reports() {
+        let smell = Smell::new(
+            PathBuf::from("gone.py"),
+            3,
+            SmellCategory::Bloat,
+            "too_many_params",
+            "Function 'f' has 5 parameters (Limit: 4).",
+        )
+        .with_context("Params: 5".to_string());
+
+        let text = strip_ansi(&render(std::slice::from_ref(&smell), &BTreeMap::new()));
+        assert!(text.contains("warning[too_many_params]: Function"));
+        assert!(text.contains("--> gone.py:3:1"));
+        assert!(text.contains("= note: Params: 5"));
+
+        // Con --stdin el fragmento sale del buffer analizado, no del disco.
+        let buffer = BTreeMap::from([(
+            PathBuf::from("gone.py"),
+            "\n\ndef f(a, b, c, d, e):\n".to_string(),
+        )]);
+        let text = strip_ansi(&render(&[smell], &buffer));
+        assert!(text.contains("3 | def f(a, b, c, d, e):"), "{}", text);
+    }
+}
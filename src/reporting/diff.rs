@@ -0,0 +1,176 @@
+use crate::core::fix::Edit;
+use std::fmt::Write as _;
+
+/// Líneas de contexto alrededor de cada cambio, como `diff -u`.
+const CONTEXT_LINES: usize = 3;
+
+/// Diff unificado entre `old` y el resultado de aplicarle `edits` (ordenadas y
+/// sin solapes, como las devuelve `fix::apply`). Los hunks salen directamente de
+/// las ediciones, sin comparar los textos línea a línea. Las rutas absolutas
+/// pierden la `/` inicial en la cabecera, como hace `git diff`.
+pub fn unified(path: &str, old: &str, edits: &[Edit]) -> String {
+    if edits.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let starts: Vec<usize> = lines
+        .iter()
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some(start)
+        })
+        .collect();
+    let line_of = |byte: usize| {
+        starts
+            .partition_point(|&start| start <= byte)
+            .saturating_sub(1)
+    };
+
+    // 1. Regiones de líneas completas que cambian (`first..end`); las ediciones
+    //    que comparten línea van en la misma región. Una inserción al principio
+    //    de una línea no la toca: su región está vacía.
+    let mut regions: Vec<Region> = Vec::new();
+    for edit in edits {
+        let first = line_of(edit.start);
+        let end = if edit.end > edit.start {
+            line_of(edit.end - 1) + 1
+        } else if starts.get(first) == Some(&edit.start) {
+            first
+        } else {
+            first + 1
+        };
+        match regions.last_mut() {
+            Some(region) if first < region.end || (first == region.end && first == end) => {
+                region.end = region.end.max(end);
+                region.edits.push(edit);
+            }
+            _ => regions.push(Region {
+                first,
+                end,
+                edits: vec![edit],
+            }),
+        }
+    }
+
+    // 2. Hunks: regiones separadas por poco contexto comparten hunk.
+    let mut out = format!(
+        "--- a/{}\n+++ b/{}\n",
+        path.trim_start_matches('/'),
+        path.trim_start_matches('/')
+    );
+    let mut delta: isize = 0;
+    let mut group_start = 0;
+    while group_start < regions.len() {
+        let mut group_end = group_start + 1;
+        while group_end < regions.len()
+            && regions[group_end].first <= regions[group_end - 1].end + 2 * CONTEXT_LINES
+        {
+            group_end += 1;
+        }
+        let group = &regions[group_start..group_end];
+        let from = group[0].first.saturating_sub(CONTEXT_LINES);
+        let to = (group[group.len() - 1].end + CONTEXT_LINES).min(lines.len());
+
+        let mut body = String::new();
+        let mut new_count = 0;
+        let mut line = from;
+        for region in group {
+            let first = region.first.min(lines.len());
+            let end = region.end.min(lines.len());
+            for context in &lines[line..first] {
+                push_line(&mut body, ' ', context);
+                new_count += 1;
+            }
+            for removed in &lines[first..end] {
+                push_line(&mut body, '-', removed);
+            }
+            let old_start = starts.get(first).copied().unwrap_or(old.len());
+            let old_stop = starts.get(end).copied().unwrap_or(old.len());
+            let replaced = region.apply(old, old_start, old_stop);
+            for added in replaced.split_inclusive('\n') {
+                push_line(&mut body, '+', added);
+                new_count += 1;
+            }
+            line = end;
+        }
+        for context in &lines[line..to] {
+            push_line(&mut body, ' ', context);
+            new_count += 1;
+        }
+
+        let old_count = to - from;
+        let new_from = (from as isize + delta) as usize;
+        let _ = writeln!(
+            out,
+            "@@ -{} +{} @@",
+            range(from, old_count),
+            range(new_from, new_count)
+        );
+        out.push_str(&body);
+        delta += new_count as isize - old_count as isize;
+        group_start = group_end;
+    }
+    out
+}
+
+/// Líneas consecutivas del original (`first..end`) y las ediciones que las tocan.
+struct Region<'e> {
+    first: usize,
+    end: usize,
+    edits: Vec<&'e Edit>,
+}
+
+impl Region<'_> {
+    /// Texto nuevo de los bytes `start..stop` del original.
+    fn apply(&self, old: &str, start: usize, stop: usize) -> String {
+        let mut text = String::new();
+        let mut cursor = start;
+        for edit in &self.edits {
+            text.push_str(&old[cursor..edit.start]);
+            text.push_str(&edit.replacement);
+            cursor = edit.end;
+        }
+        text.push_str(&old[cursor..stop]);
+        text
+    }
+}
+
+/// `inicio,cantidad` en base 1; un rango vacío se numera por la línea anterior.
+fn range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, count)
+    }
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    out.push(prefix);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hunks_from_edits() {
+        let old = "a\nb  \nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm";
+        let edits = [
+            Edit::delete(3, 5),
+            Edit::insert(8, "z\n"),
+            Edit::insert(old.len(), "\n"),
+        ];
+        let diff = unified("src/x.py", old, &edits);
+        assert_eq!(
+            diff,
+            "--- a/src/x.py\n+++ b/src/x.py\n\
+             @@ -1,6 +1,7 @@\n a\n-b  \n+b\n c\n+z\n d\n e\n f\n\
+             @@ -10,4 +11,4 @@\n j\n k\n l\n-m\n\\ No newline at end of file\n+m\n"
+        );
+    }
+}
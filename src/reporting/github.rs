@@ -0,0 +1,79 @@
+use super::{report_path, Report, Reporter};
+use crate::core::rules::{Severity, Smell};
+use std::io::{self, Write};
+
+pub struct GithubReporter;
+
+impl Reporter for GithubReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render(report.smells()).as_bytes())
+    }
+}
+
+/// Un workflow command por smell; GitHub los convierte en anotaciones del diff.
+/// Ver <https://docs.github.com/actions/reference/workflow-commands-for-github-actions>.
+fn render(smells: &[Smell]) -> String {
+    smells
+        .iter()
+        .map(|smell| {
+            format!(
+                "::{} file={},line={},title={}::{}\n",
+                command(smell.severity()),
+                escape_property(&report_path(&smell.file_path)),
+                smell.line,
+                escape_property(&smell.rule_id),
+                escape_data(&smell.message)
+            )
+        })
+        .collect()
+}
+
+fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "notice",
+        Severity::Warning => "warning",
+    }
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::SmellCategory;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_workflow_commands() {
+        let smells = vec![
+            Smell::new(
+                PathBuf::from("./src/a,b.rs"),
+                7,
+                SmellCategory::Bloat,
+                "long_function",
+                "100% too long\nreally",
+            ),
+            Smell::new(
+                PathBuf::from("src/b.rs"),
+                2,
+                SmellCategory::Hygiene,
+                "todo_comment",
+                "Found technical debt anchor (TODO/FIXME).",
+            ),
+        ];
+
+        assert_eq!(
+            render(&smells),
+            "::warning file=src/a%2Cb.rs,line=7,title=long_function::100%25 too long%0Areally\n\
+             ::notice file=src/b.rs,line=2,title=todo_comment::Found technical debt anchor (TODO/FIXME).\n"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use super::{fingerprints, report_path, Report, Reporter};
+use crate::core::rules::{Severity, Smell, SmellCategory};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Una entrada del reporte Code Quality de GitLab.
+/// Ver <https://docs.gitlab.com/ci/testing/code_quality/#code-quality-report-format>.
+#[derive(Serialize)]
+struct Issue<'a> {
+    description: &'a str,
+    check_name: &'a str,
+    fingerprint: String,
+    severity: &'static str,
+    categories: Vec<String>,
+    location: Location,
+}
+
+#[derive(Serialize)]
+struct Location {
+    path: String,
+    lines: Lines,
+}
+
+#[derive(Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+pub struct GitlabReporter;
+
+impl Reporter for GitlabReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, &build_issues(report.smells()))?;
+        writeln!(out)
+    }
+}
+
+fn build_issues(smells: &[Smell]) -> Vec<Issue<'_>> {
+    smells
+        .iter()
+        .zip(fingerprints(smells))
+        .map(|(smell, fingerprint)| Issue {
+            description: &smell.message,
+            check_name: &smell.rule_id,
+            fingerprint,
+            severity: severity(smell.severity()),
+            categories: vec![category(smell)],
+            location: Location {
+                path: report_path(&smell.file_path),
+                lines: Lines { begin: smell.line },
+            },
+        })
+        .collect()
+}
+
+/// GitLab acepta: info, minor, major, critical, blocker.
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warning => "minor",
+    }
+}
+
+/// Categorías del formato Code Climate que usa GitLab.
+fn category(smell: &Smell) -> String {
+    match smell.category {
+        // Funciones y archivos demasiado grandes: Code Climate los trata como complejidad.
+        SmellCategory::Complexity | SmellCategory::Bloat => "Complexity",
+        SmellCategory::Hygiene | SmellCategory::Naming => "Style",
+        SmellCategory::Design | SmellCategory::Unknown => "Clarity",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_code_quality_issue() {
+        let smells = vec![Smell::new(
+            PathBuf::from("./lib/tool.py"),
+            21,
+            SmellCategory::Complexity,
+            "deep_nesting",
+            "Logic is deeply nested (Depth: 5). Flatten your code.",
+        )];
+
+        let json = serde_json::to_value(build_issues(&smells)).unwrap();
+        let issue = &json[0];
+        assert_eq!(issue["check_name"], "deep_nesting");
+        assert_eq!(issue["severity"], "minor");
+        assert_eq!(issue["location"]["path"], "lib/tool.py");
+        assert_eq!(issue["location"]["lines"]["begin"], 21);
+        assert_eq!(issue["fingerprint"].as_str().unwrap().len(), 16);
+        assert_eq!(issue["categories"], serde_json::json!(["Complexity"]));
+
+        let long = Smell::new(
+            PathBuf::from("a.py"),
+            1,
+            SmellCategory::Bloat,
+            "long_function",
+            "",
+        );
+        assert_eq!(category(&long), "Complexity");
+    }
+}
@@ -0,0 +1,394 @@
+use super::{group_by_file, read_source, report_path, xml::escape, Report, Reporter};
+use crate::core::rules::{Smell, SmellCategory};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Hoja de estilos embebida: el reporte no puede depender de recursos externos.
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 0 auto; max-width: 1200px; padding: 1rem 2rem; color: #1f2328; }
+h1 { border-bottom: 2px solid #d0d7de; padding-bottom: .3rem; }
+a { color: #0969da; text-decoration: none; }
+.cards { display: flex; gap: 1rem; flex-wrap: wrap; }
+.card { border: 1px solid #d0d7de; border-radius: 6px; padding: .6rem 1rem; min-width: 7rem; }
+.card b { display: block; font-size: 1.6rem; }
+.COMPLEXITY { color: #cf222e; } .BLOAT { color: #9a6700; } .HYGIENE { color: #0550ae; }
+.NAMING { color: #1b7c83; } .DESIGN { color: #8250df; } .UNKNOWN { color: #57606a; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border-bottom: 1px solid #d0d7de; padding: .3rem .5rem; text-align: left; vertical-align: top; }
+#smells th { cursor: pointer; user-select: none; background: #f6f8fa; }
+.controls { margin: .5rem 0; display: flex; gap: .5rem; }
+.tree ul { list-style: none; padding-left: 1.2rem; margin: 0; }
+.bar { display: inline-block; height: .6rem; background: #cf222e; margin-left: .5rem; vertical-align: middle; }
+.file-page { display: none; }
+.file-page:target, .file-page:has(:target) { display: block; }
+.source { font-family: ui-monospace, monospace; font-size: .85rem; }
+.source td { border: none; padding: 0 .5rem; white-space: pre; }
+.source td.ln { color: #8c959f; text-align: right; user-select: none; }
+.source tr.hit { background: #fff8c5; }
+.source tr:target { outline: 2px solid #d4a72c; }
+"#;
+
+/// Ordenación y filtrado de la tabla de smells, sin dependencias.
+const SCRIPT: &str = r#"
+const table = document.getElementById('smells');
+const rows = () => Array.from(table.tBodies[0].rows);
+table.querySelectorAll('th').forEach((th, col) => {
+  th.addEventListener('click', () => {
+    const asc = th.dataset.dir !== 'asc';
+    th.dataset.dir = asc ? 'asc' : 'desc';
+    const numeric = th.dataset.type === 'number';
+    const key = (r) => numeric ? Number(r.cells[col].dataset.value) : r.cells[col].textContent;
+    rows().sort((a, b) => {
+      const [x, y] = [key(a), key(b)];
+      const cmp = numeric ? x - y : String(x).localeCompare(String(y));
+      return asc ? cmp : -cmp;
+    }).forEach((r) => table.tBodies[0].appendChild(r));
+  });
+});
+const text = document.getElementById('filter');
+const category = document.getElementById('category');
+function applyFilter() {
+  const needle = text.value.toLowerCase();
+  rows().forEach((r) => {
+    const okText = r.textContent.toLowerCase().includes(needle);
+    const okCat = !category.value || r.dataset.category === category.value;
+    r.style.display = okText && okCat ? '' : 'none';
+  });
+}
+text.addEventListener('input', applyFilter);
+category.addEventListener('change', applyFilter);
+"#;
+
+pub struct HtmlReporter;
+
+impl Reporter for HtmlReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render(report.smells(), report.sources()).as_bytes())
+    }
+}
+
+/// Un archivo con smells, con la fuente analizada (si sigue disponible).
+struct FileView<'a> {
+    anchor: String,
+    path: String,
+    smells: Vec<&'a Smell>,
+    source: Option<Cow<'a, str>>,
+}
+
+fn render(smells: &[Smell], sources: &BTreeMap<PathBuf, String>) -> String {
+    let files: Vec<FileView> = group_by_file(smells)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (path, file_smells))| FileView {
+            anchor: format!("file-{}", index),
+            path: report_path(path),
+            smells: file_smells,
+            source: read_source(sources, path),
+        })
+        .collect();
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>lint report</title>\n");
+    let _ = writeln!(out, "<style>{}</style>\n</head>\n<body>", STYLE);
+    out.push_str("<h1>lint report</h1>\n");
+
+    render_summary(&mut out, smells);
+    render_tree(&mut out, &files);
+    render_smell_table(&mut out, &files);
+    for file in &files {
+        render_file_page(&mut out, file);
+    }
+
+    let _ = writeln!(out, "<script>{}</script>\n</body>\n</html>", SCRIPT);
+    out
+}
+
+fn render_summary(out: &mut String, smells: &[Smell]) {
+    let mut by_category: BTreeMap<String, usize> =
+        CATEGORIES.iter().map(|c| (c.to_string(), 0)).collect();
+    for smell in smells {
+        *by_category.entry(smell.category.to_string()).or_insert(0) += 1;
+    }
+
+    out.push_str("<h2>Summary</h2>\n<div class=\"cards\">\n");
+    for category in CATEGORIES {
+        let name = category.to_string();
+        let _ = writeln!(
+            out,
+            "<div class=\"card {}\"><b>{}</b>{}</div>",
+            name, by_category[&name], name
+        );
+    }
+    let _ = writeln!(
+        out,
+        "<div class=\"card\"><b>{}</b>TOTAL</div>\n</div>",
+        smells.len()
+    );
+}
+
+const CATEGORIES: [SmellCategory; 5] = [
+    SmellCategory::Complexity,
+    SmellCategory::Bloat,
+    SmellCategory::Hygiene,
+    SmellCategory::Naming,
+    SmellCategory::Design,
+];
+
+/// Nodo del árbol de directorios: smells y líneas acumuladas de todo el subárbol.
+#[derive(Default)]
+struct DirNode<'a> {
+    smells: usize,
+    lines: usize,
+    dirs: BTreeMap<String, DirNode<'a>>,
+    files: Vec<&'a FileView<'a>>,
+}
+
+impl<'a> DirNode<'a> {
+    fn insert(&mut self, file: &'a FileView<'a>) {
+        let lines = file.source.as_deref().map_or(0, |s| s.lines().count());
+        let mut node = self;
+        node.add(file.smells.len(), lines);
+        let mut parts: Vec<&str> = file.path.split('/').collect();
+        parts.pop();
+        for part in parts {
+            node = node.dirs.entry(part.to_string()).or_default();
+            node.add(file.smells.len(), lines);
+        }
+        node.files.push(file);
+    }
+
+    fn add(&mut self, smells: usize, lines: usize) {
+        self.smells += smells;
+        self.lines += lines;
+    }
+}
+
+/// Densidad en smells por cada 100 líneas (de los archivos con smells).
+fn density(smells: usize, lines: usize) -> f64 {
+    if lines == 0 {
+        return 0.0;
+    }
+    smells as f64 * 100.0 / lines as f64
+}
+
+fn render_tree(out: &mut String, files: &[FileView]) {
+    let mut root = DirNode::default();
+    for file in files {
+        root.insert(file);
+    }
+
+    out.push_str("<h2>Smell density</h2>\n");
+    out.push_str("<p>Smells per 100 lines, over files with at least one smell.</p>\n");
+    out.push_str("<div class=\"tree\">\n");
+    render_dir(out, ".", &root);
+    out.push_str("</div>\n");
+}
+
+fn render_dir(out: &mut String, name: &str, node: &DirNode) {
+    let value = density(node.smells, node.lines);
+    let _ = writeln!(
+        out,
+        "<details open><summary>{}/ — {} smells, {:.1}{}</summary>\n<ul>",
+        escape(name),
+        node.smells,
+        value,
+        bar(value)
+    );
+    for (child_name, child) in &node.dirs {
+        out.push_str("<li>");
+        render_dir(out, child_name, child);
+        out.push_str("</li>\n");
+    }
+    for file in &node.files {
+        let lines = file.source.as_deref().map_or(0, |s| s.lines().count());
+        let value = density(file.smells.len(), lines);
+        let name = file.path.rsplit('/').next().unwrap_or(&file.path);
+        let _ = writeln!(
+            out,
+            "<li><a href=\"#{}\">{}</a> — {} smells, {:.1}{}</li>",
+            file.anchor,
+            escape(name),
+            file.smells.len(),
+            value,
+            bar(value)
+        );
+    }
+    out.push_str("</ul>\n</details>\n");
+}
+
+fn bar(value: f64) -> String {
+    let width = (value * 4.0).clamp(1.0, 200.0);
+    format!("<span class=\"bar\" style=\"width:{:.0}px\"></span>", width)
+}
+
+fn render_smell_table(out: &mut String, files: &[FileView]) {
+    out.push_str("<h2>Smells</h2>\n<div class=\"controls\">\n");
+    out.push_str("<input id=\"filter\" type=\"search\" placeholder=\"Filter…\">\n");
+    out.push_str("<select id=\"category\"><option value=\"\">All categories</option>");
+    for category in CATEGORIES {
+        let _ = write!(out, "<option>{}</option>", category);
+    }
+    out.push_str("</select>\n</div>\n");
+
+    out.push_str("<table id=\"smells\">\n<thead><tr><th>Category</th><th>Rule</th>");
+    out.push_str(
+        "<th>File</th><th data-type=\"number\">Line</th><th>Message</th></tr></thead>\n<tbody>\n",
+    );
+    for file in files {
+        for smell in &file.smells {
+            let _ = writeln!(
+                out,
+                "<tr data-category=\"{cat}\"><td class=\"{cat}\">{cat}</td><td>{rule}</td>\
+                 <td><a href=\"#{anchor}\">{path}</a></td>\
+                 <td data-value=\"{line}\"><a href=\"#{anchor}-L{line}\">{line}</a></td><td>{msg}</td></tr>",
+                cat = smell.category,
+                rule = escape(&smell.rule_id),
+                anchor = file.anchor,
+                path = escape(&file.path),
+                line = smell.line,
+                msg = escape(&smell.message)
+            );
+        }
+    }
+    out.push_str("</tbody>\n</table>\n");
+}
+
+fn render_file_page(out: &mut String, file: &FileView) {
+    let _ = writeln!(
+        out,
+        "<section class=\"file-page\" id=\"{}\">\n<h2>{}</h2>\n<p><a href=\"#\">↑ back to summary</a></p>",
+        file.anchor,
+        escape(&file.path)
+    );
+
+    let mut messages: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for smell in &file.smells {
+        messages
+            .entry(smell.line)
+            .or_default()
+            .push(format!("[{}] {}", smell.rule_id, smell.message));
+    }
+
+    out.push_str("<ul>\n");
+    for (line, texts) in &messages {
+        for text in texts {
+            let _ = writeln!(
+                out,
+                "<li><a href=\"#{}-L{}\">line {}</a>: {}</li>",
+                file.anchor,
+                line,
+                line,
+                escape(text)
+            );
+        }
+    }
+    out.push_str("</ul>\n");
+
+    match &file.source {
+        Some(source) => {
+            let hits: BTreeSet<usize> = messages.keys().copied().collect();
+            out.push_str("<table class=\"source\">\n");
+            for (index, text) in source.lines().enumerate() {
+                let line = index + 1;
+                let class = if hits.contains(&line) {
+                    " class=\"hit\""
+                } else {
+                    ""
+                };
+                let title = messages
+                    .get(&line)
+                    .map(|t| format!(" title=\"{}\"", escape(&t.join("\n"))))
+                    .unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "<tr id=\"{}-L{}\"{}{}><td class=\"ln\">{}</td><td>{}</td></tr>",
+                    file.anchor,
+                    line,
+                    class,
+                    title,
+                    line,
+                    escape(text)
+                );
+            }
+            out.push_str("</table>\n");
+        }
+        None => out.push_str("<p><em>Source not available.</em></p>\n"),
+    }
+    out.push_str("</section>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn smell(path: &str, line: usize, category: SmellCategory, message: &str) -> Smell {
+        Smell::new(PathBuf::from(path), line, category, "rule", message)
+    }
+
+    #[test]
+    fn test_html_is_self_contained() {
+        let html = render(
+            &[smell(
+                "missing/dir/app.py",
+                3,
+                SmellCategory::Naming,
+                "Variable '<d>' is too short.",
+            )],
+            &BTreeMap::new(),
+        );
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("src=\"http") && !html.contains("href=\"http"));
+        assert!(html.contains("Variable &apos;&lt;d&gt;&apos; is too short."));
+        assert!(html.contains("<section class=\"file-page\" id=\"file-0\">"));
+        assert!(html.contains("Source not available."));
+    }
+
+    #[test]
+    fn test_source_lines_are_highlighted() {
+        // Este mismo archivo existe en disco durante los tests.
+        let path = "src/reporting/html.rs";
+        let html = render(
+            &[smell(path, 2, SmellCategory::Bloat, "boom")],
+            &BTreeMap::new(),
+        );
+
+        assert!(html.contains("<tr id=\"file-0-L2\" class=\"hit\" title=\"[rule] boom\">"));
+        assert!(html.contains("<tr id=\"file-0-L1\"><td class=\"ln\">1</td>"));
+    }
+
+    #[test]
+    fn test_source_view_keeps_tabs_and_quotes() {
+        let sources = BTreeMap::from([(PathBuf::from("t.py"), "if a:\n\tx = '<'\n".to_string())]);
+        let html = render(
+            &[
+                smell("t.py", 2, SmellCategory::Naming, "one"),
+                smell("t.py", 2, SmellCategory::Naming, "two"),
+            ],
+            &sources,
+        );
+
+        assert!(html.contains("title=\"[rule] one&#10;[rule] two\""));
+        assert!(html.contains("<td>&#9;x = &apos;&lt;&apos;</td>"));
+    }
+
+    #[test]
+    fn test_directory_tree_accumulates_counts() {
+        let html = render(
+            &[
+                smell("a/b/x.py", 1, SmellCategory::Bloat, "m"),
+                smell("a/b/y.py", 1, SmellCategory::Bloat, "m"),
+                smell("a/z.py", 1, SmellCategory::Bloat, "m"),
+            ],
+            &BTreeMap::new(),
+        );
+
+        assert!(html.contains("<summary>a/ — 3 smells"));
+        assert!(html.contains("<summary>b/ — 2 smells"));
+    }
+}
@@ -1,47 +1,450 @@
+use super::{Report, Reporter};
+use crate::analysis::engine::AnalysisError;
+use crate::analysis::languages::Language;
+use crate::core::config::LintConfig;
 use crate::core::rules::{Smell, SmellCategory};
 use serde::Serialize;
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// Versión del esquema del reporte JSON.
+///
+/// Reglas de compatibilidad: añadir campos no cambia la versión (los consumidores
+/// deben ignorar los que no conozcan); quitar, renombrar o cambiar el tipo de un
+/// campo existente sí, y exige subirla. Los tests de este módulo fijan los campos
+/// de cada versión publicada.
+pub const SCHEMA_VERSION: u32 = 2;
 
 /// Estructura raíz del reporte JSON.
 #[derive(Serialize)]
 struct JsonReport<'a> {
-    summary: Summary,
+    schema_version: u32,
+    tool: ToolInfo,
+    config: &'a LintConfig,
+    summary: Summary<'a>,
+    errors: &'a [AnalysisError],
     smells: &'a [Smell],
 }
 
+#[derive(Serialize)]
+pub(super) struct ToolInfo {
+    name: &'static str,
+    version: &'static str,
+}
+
+impl ToolInfo {
+    pub(super) fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
+
 /// Resumen estadístico para facilitar el parseo en CI.
 #[derive(Serialize)]
-struct Summary {
+struct Summary<'a> {
     total_smells: usize,
-    by_category: HashMap<SmellCategory, usize>,
+    by_category: BTreeMap<SmellCategory, usize>,
+    files_analyzed: usize,
+    by_language: &'a BTreeMap<Language, usize>,
+    unsupported_files: &'a BTreeMap<&'static str, usize>,
+    duration_ms: u64,
 }
 
-pub fn print_report(smells: &[Smell]) {
-    // 1. Calcular Estadísticas
-    let mut by_category = HashMap::new();
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, &build_report(report))?;
+        writeln!(out)
+    }
+}
 
-    // Inicializar a 0 para que siempre aparezcan todas las categorías (opcional, pero limpio)
-    by_category.insert(SmellCategory::Complexity, 0);
-    by_category.insert(SmellCategory::Bloat, 0);
-    by_category.insert(SmellCategory::Hygiene, 0);
-    by_category.insert(SmellCategory::Naming, 0);
-    by_category.insert(SmellCategory::Design, 0);
+fn build_report<'a>(report: &Report<'a>) -> JsonReport<'a> {
+    let analysis = report.analysis;
 
-    for smell in smells {
+    // 1. Calcular Estadísticas
+    let mut by_category = empty_category_counts();
+    for smell in &analysis.smells {
         *by_category.entry(smell.category).or_insert(0) += 1;
     }
 
-    let report = JsonReport {
+    JsonReport {
+        schema_version: SCHEMA_VERSION,
+        tool: ToolInfo::current(),
+        config: report.config,
         summary: Summary {
-            total_smells: smells.len(),
+            total_smells: analysis.smells.len(),
             by_category,
+            files_analyzed: analysis.files_analyzed(),
+            by_language: &analysis.analyzed,
+            unsupported_files: &analysis.unsupported,
+            duration_ms: analysis.duration.as_millis() as u64,
+        },
+        errors: &analysis.errors,
+        smells: &analysis.smells,
+    }
+}
+
+/// Contadores a 0 para que siempre aparezcan todas las categorías.
+pub(super) fn empty_category_counts() -> BTreeMap<SmellCategory, usize> {
+    [
+        SmellCategory::Complexity,
+        SmellCategory::Bloat,
+        SmellCategory::Hygiene,
+        SmellCategory::Naming,
+        SmellCategory::Design,
+    ]
+    .into_iter()
+    .map(|category| (category, 0))
+    .collect()
+}
+
+/// JSON Schema (draft 2020-12) del reporte, publicado con `lint schema`.
+pub fn schema() -> Value {
+    let count = json!({ "type": "integer", "minimum": 0 });
+    let counts = json!({ "type": "object", "additionalProperties": count });
+    let position = json!({ "type": "integer", "minimum": 1 });
+    let fix = json!({
+        "description": "Fix for the smell; --fix only applies the automatic ones. Offsets are bytes into the analyzed file, `end` exclusive.",
+        "type": "object",
+        "required": ["description", "applicability", "edits"],
+        "properties": {
+            "description": { "type": "string" },
+            "applicability": { "enum": ["automatic", "suggestion"] },
+            "edits": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["start", "end", "replacement"],
+                    "properties": {
+                        "start": count,
+                        "end": count,
+                        "replacement": { "type": "string" }
+                    }
+                }
+            }
+        }
+    });
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "lint JSON report",
+        "type": "object",
+        "required": ["schema_version", "tool", "config", "summary", "errors", "smells"],
+        "properties": {
+            "schema_version": { "const": SCHEMA_VERSION },
+            "tool": {
+                "type": "object",
+                "required": ["name", "version"],
+                "properties": {
+                    "name": { "type": "string" },
+                    "version": { "type": "string" }
+                }
+            },
+            "config": {
+                "description": "Effective configuration after lint.toml, --strict, --lang and --baseline.",
+                "type": "object",
+                "required": ["thresholds", "languages"],
+                "properties": {
+                    "thresholds": {
+                        "type": "object",
+                        "required": ["max_complexity", "max_function_lines", "max_params", "max_file_lines", "max_dir_files", "max_nesting_depth"],
+                        "properties": {
+                            "max_complexity": count,
+                            "max_function_lines": count,
+                            "max_params": count,
+                            "max_file_lines": count,
+                            "max_dir_files": count,
+                            "max_nesting_depth": {
+                                "type": "object",
+                                "required": ["rust", "python"],
+                                "properties": { "rust": count, "python": count }
+                            }
+                        }
+                    },
+                    "languages": {
+                        "type": "object",
+                        "required": ["extensions", "filenames"],
+                        "properties": {
+                            "extensions": { "type": "object", "additionalProperties": { "type": "string" } },
+                            "filenames": { "type": "object", "additionalProperties": { "type": "string" } }
+                        }
+                    },
+                    "lang": { "enum": ["en", "es"] },
+                    "baseline": {
+                        "description": "Baseline file whose smells are not reported (see `lint baseline`).",
+                        "type": "string"
+                    },
+                    "rules": {
+                        "description": "Rules turned on or off by id, over their default.",
+                        "type": "object",
+                        "additionalProperties": { "type": "boolean" }
+                    }
+                }
+            },
+            "summary": {
+                "type": "object",
+                "required": ["total_smells", "by_category", "files_analyzed", "by_language", "unsupported_files", "duration_ms"],
+                "properties": {
+                    "total_smells": count,
+                    "by_category": counts,
+                    "files_analyzed": count,
+                    "by_language": counts,
+                    "unsupported_files": counts,
+                    "duration_ms": count
+                }
+            },
+            "errors": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["path", "message"],
+                    "properties": {
+                        "path": { "type": "string" },
+                        "message": { "type": "string" }
+                    }
+                }
+            },
+            "smells": { "type": "array", "items": { "$ref": "#/$defs/smell" } }
         },
-        smells,
-    };
+        "$defs": {
+            "smell": {
+                "type": "object",
+                "required": ["file_path", "line", "column", "end_line", "end_column", "category", "rule_id", "message", "context"],
+                "properties": {
+                    "file_path": { "type": "string" },
+                    "line": position,
+                    "column": position,
+                    "end_line": position,
+                    "end_column": position,
+                    "symbol": {
+                        "description": "Enclosing symbol path, e.g. crate::engine::run or module.Class.method.",
+                        "type": "string"
+                    },
+                    "category": { "enum": ["Complexity", "Bloat", "Hygiene", "Design", "Naming", "Unknown"] },
+                    "rule_id": { "type": "string" },
+                    "message": { "type": "string" },
+                    "message_key": {
+                        "description": "Catalog key of `message`; absent for messages outside the catalog.",
+                        "type": "string"
+                    },
+                    "message_args": {
+                        "description": "Parameters of the catalog message, by name.",
+                        "type": "object",
+                        "additionalProperties": { "type": ["string", "integer"] }
+                    },
+                    "context": { "type": ["string", "null"] },
+                    "metric": {
+                        "description": "Measured value and configured limit of threshold rules.",
+                        "type": "object",
+                        "required": ["name", "value", "limit"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "value": count,
+                            "limit": count
+                        }
+                    },
+                    "fix": fix
+                }
+            }
+        }
+    })
+}
+
+pub fn print_schema() -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    serde_json::to_writer_pretty(&mut out, &schema())?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::engine::Analysis;
+    use crate::core::fix::{Edit, Fix};
+    use crate::core::messages::{Lang, MessageArgs};
+    use crate::reporting::terminal::Terminal;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn localized(mut smell: Smell) -> Smell {
+        smell.localize(Lang::Es);
+        smell
+    }
+
+    fn sample_report() -> Value {
+        let analysis = Analysis {
+            smells: vec![
+                localized(
+                    Smell::from_catalog(
+                        PathBuf::from("src/main.rs"),
+                        12,
+                        SmellCategory::Bloat,
+                        "long_function",
+                        MessageArgs::from([
+                            ("name", "main".into()),
+                            ("lines", 80.into()),
+                            ("limit", 40.into()),
+                        ]),
+                    )
+                    .with_context("Lines: 80".to_string())
+                    .with_metric("lines", 80, 40)
+                    .with_symbol("crate::main"),
+                ),
+                Smell::new(
+                    PathBuf::from("src/lib.rs"),
+                    3,
+                    SmellCategory::Design,
+                    "unwrap_in_result",
+                    "unwrap() in a function that returns Result.",
+                )
+                .with_fix(Fix::suggestion(
+                    "Replace unwrap() with ?",
+                    vec![Edit::replace(10, 19, "?")],
+                )),
+            ],
+            total_smells: 2,
+            analyzed: BTreeMap::from([(Language::Rust, 3)]),
+            files: ["src/lib.rs", "src/main.rs", "src/util.rs"]
+                .map(PathBuf::from)
+                .to_vec(),
+            unsupported: BTreeMap::from([("go", 1)]),
+            errors: vec![AnalysisError {
+                path: PathBuf::from("src/locked.rs"),
+                message: "Permission denied".to_string(),
+            }],
+            functions: vec![],
+            duration: Duration::from_millis(42),
+            sources: BTreeMap::new(),
+        };
+        let config = LintConfig {
+            lang: Some(Lang::Es),
+            ..LintConfig::default()
+        };
+        let report = Report {
+            analysis: &analysis,
+            config: &config,
+            terminal: Terminal::PLAIN,
+            console: Default::default(),
+        };
+        serde_json::to_value(build_report(&report)).unwrap()
+    }
+
+    /// Errores de validar `value` contra `schema` con `jsonschema`.
+    fn schema_errors(schema: &Value, value: &Value) -> Vec<String> {
+        jsonschema::validator_for(schema)
+            .unwrap()
+            .iter_errors(value)
+            .map(|error| format!("{} at {}", error, error.instance_path()))
+            .collect()
+    }
+
+    /// El esquema publicado con cada objeto cerrado (`additionalProperties:
+    /// false`), para detectar campos que el reporte emite sin declararlos.
+    fn closed(mut schema: Value) -> Value {
+        match &mut schema {
+            Value::Object(fields) => {
+                if fields.contains_key("properties") && !fields.contains_key("additionalProperties")
+                {
+                    fields.insert("additionalProperties".to_string(), Value::Bool(false));
+                }
+                for value in fields.values_mut() {
+                    *value = closed(value.take());
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    *item = closed(item.take());
+                }
+            }
+            _ => {}
+        }
+        schema
+    }
+
+    #[test]
+    fn test_report_conforms_to_published_schema() {
+        let errors = schema_errors(&schema(), &sample_report());
+        assert!(errors.is_empty(), "{:#?}", errors);
+
+        let errors = schema_errors(&closed(schema()), &sample_report());
+        assert!(errors.is_empty(), "undeclared fields: {:#?}", errors);
+
+        let mut broken = sample_report();
+        broken["smells"][0]["line"] = json!(0);
+        broken["summary"]["undeclared"] = json!(1);
+        assert_eq!(schema_errors(&closed(schema()), &broken).len(), 2);
+    }
+
+    #[test]
+    fn test_report_fields() {
+        let report = sample_report();
+        assert_eq!(report["summary"]["by_language"]["rust"], 3);
+        assert_eq!(report["summary"]["unsupported_files"]["go"], 1);
+        assert_eq!(report["summary"]["duration_ms"], 42);
+        assert_eq!(report["errors"][0]["path"], "src/locked.rs");
+        assert_eq!(report["config"]["thresholds"]["max_params"], 4);
+
+        let smell = &report["smells"][0];
+        assert_eq!(
+            smell["message"],
+            "La función 'main' ocupa 80 líneas (Límite: 40)."
+        );
+        assert_eq!(smell["message_key"], "long_function");
+        assert_eq!(smell["message_args"]["name"], "main");
+        assert_eq!(smell["message_args"]["lines"], 80);
+        assert_eq!(smell["metric"]["name"], "lines");
+        assert_eq!(smell["metric"]["value"], 80);
+        assert_eq!(smell["metric"]["limit"], 40);
+        assert_eq!(smell["symbol"], "crate::main");
+        assert_eq!(report["smells"][1]["fix"]["applicability"], "suggestion");
+    }
+
+    /// Campos del formato original (sin `schema_version`), en los que se apoyan
+    /// los dashboards existentes. No deben desaparecer ni cambiar de tipo.
+    #[test]
+    fn test_v1_fields_are_preserved() {
+        let report = sample_report();
+        assert!(report["summary"]["total_smells"].is_u64());
+
+        let by_category = report["summary"]["by_category"].as_object().unwrap();
+        for category in ["Complexity", "Bloat", "Hygiene", "Naming", "Design"] {
+            assert!(
+                by_category[category].is_u64(),
+                "missing category {}",
+                category
+            );
+        }
+
+        let smell = &report["smells"][0];
+        assert_eq!(smell["file_path"], "src/main.rs");
+        assert_eq!(smell["line"], 12);
+        assert_eq!(smell["category"], "Bloat");
+        assert_eq!(smell["rule_id"], "long_function");
+        assert!(smell["message"].is_string());
+        assert_eq!(smell["context"], "Lines: 80");
+    }
 
-    // 2. Serializar a String
-    match serde_json::to_string_pretty(&report) {
-        Ok(json) => println!("{}", json),
-        Err(e) => eprintln!("Failed to generate JSON report: {}", e),
+    /// Campos añadidos en la versión 2.
+    #[test]
+    fn test_v2_fields_are_preserved() {
+        let report = sample_report();
+        assert_eq!(report["schema_version"], 2);
+        assert_eq!(report["tool"]["name"], env!("CARGO_PKG_NAME"));
+        assert!(report["tool"]["version"].is_string());
+        for key in [
+            "files_analyzed",
+            "by_language",
+            "unsupported_files",
+            "duration_ms",
+        ] {
+            assert!(!report["summary"][key].is_null(), "missing summary.{}", key);
+        }
+        assert!(report["errors"].is_array());
+        assert!(report["config"]["languages"].is_object());
     }
 }
@@ -0,0 +1,175 @@
+use super::{group_by_file, report_path};
+use super::{xml::escape, Report, Reporter};
+use crate::core::rules::Smell;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+pub struct JunitReporter;
+
+impl Reporter for JunitReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render(report.files(), report.smells()).as_bytes())
+    }
+}
+
+/// Cada archivo analizado es un `<testcase>`: pasa si está limpio y, si no,
+/// lleva un único `<failure>` (el esquema de JUnit no admite más) que lista
+/// todos sus smells, uno por línea. Sin archivos se emite un caso que pasa,
+/// para que el CI no vea un suite vacío.
+fn render(analyzed: &[PathBuf], smells: &[Smell]) -> String {
+    let mut files = group_by_file(smells);
+    let failures = files.len();
+    for path in analyzed {
+        files.entry(path).or_default();
+    }
+    let tests = files.len().max(1);
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"lint\" tests=\"{}\" failures=\"{}\">",
+        tests, failures
+    );
+    let _ = writeln!(
+        out,
+        "  <testsuite name=\"lint\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">",
+        tests, failures
+    );
+
+    if files.is_empty() {
+        out.push_str("    <testcase classname=\"lint\" name=\"lint\"/>\n");
+    }
+
+    for (path, file_smells) in files {
+        let name = escape(&report_path(path));
+        if file_smells.is_empty() {
+            let _ = writeln!(
+                out,
+                "    <testcase classname=\"lint\" name=\"{}\" file=\"{}\"/>",
+                name, name
+            );
+            continue;
+        }
+        let _ = writeln!(
+            out,
+            "    <testcase classname=\"lint\" name=\"{}\" file=\"{}\">",
+            name, name
+        );
+        let (kind, message) = match file_smells.as_slice() {
+            [smell] => (smell.rule_id.clone(), smell.message.clone()),
+            many => {
+                let mut rules: Vec<&str> = many.iter().map(|s| s.rule_id.as_str()).collect();
+                rules.sort_unstable();
+                rules.dedup();
+                (rules.join(" "), format!("{} smells", many.len()))
+            }
+        };
+        let body: Vec<String> = file_smells.iter().map(|s| failure_body(s)).collect();
+        let _ = writeln!(
+            out,
+            "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+            escape(&kind),
+            escape(&message),
+            escape(&body.join("\n"))
+        );
+        out.push_str("    </testcase>\n");
+    }
+
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    out
+}
+
+/// Texto que Jenkins muestra al expandir el fallo.
+fn failure_body(smell: &Smell) -> String {
+    let mut body = format!(
+        "{}:{}: [{}/{}] {}",
+        report_path(&smell.file_path),
+        smell.line,
+        smell.category,
+        smell.rule_id,
+        smell.message
+    );
+    if let Some(context) = &smell.context {
+        body.push_str(" (");
+        body.push_str(context);
+        body.push(')');
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::SmellCategory;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_junit_file_per_testcase() {
+        let smells = vec![Smell::new(
+            PathBuf::from("app/main.py"),
+            4,
+            SmellCategory::Hygiene,
+            "todo_comment",
+            "Found technical debt anchor (TODO/FIXME).",
+        )];
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="lint" tests="1" failures="1">
+  <testsuite name="lint" tests="1" failures="1" errors="0" skipped="0">
+    <testcase classname="lint" name="app/main.py" file="app/main.py">
+      <failure type="todo_comment" message="Found technical debt anchor (TODO/FIXME).">app/main.py:4: [HYGIENE/todo_comment] Found technical debt anchor (TODO/FIXME).</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#;
+        assert_eq!(render(&[PathBuf::from("app/main.py")], &smells), expected);
+    }
+
+    #[test]
+    fn test_junit_one_failure_per_file() {
+        let smell = |path: &str, line, rule: &str| {
+            Smell::new(PathBuf::from(path), line, SmellCategory::Naming, rule, "m")
+        };
+        let xml = render(
+            &[],
+            &[
+                smell("a.py", 1, "short_variable"),
+                smell("a.py", 5, "bad_naming"),
+                smell("a.py", 9, "short_variable"),
+                smell("b.py", 2, "short_variable"),
+            ],
+        );
+        assert!(xml.contains("<testsuites name=\"lint\" tests=\"2\" failures=\"2\">"));
+        assert_eq!(xml.matches("<failure ").count(), 2);
+        assert!(xml.contains(
+            "<failure type=\"bad_naming short_variable\" message=\"3 smells\">\
+             a.py:1: [NAMING/short_variable] m&#10;a.py:5: [NAMING/bad_naming] m&#10;a.py:9: [NAMING/short_variable] m</failure>"
+        ));
+    }
+
+    #[test]
+    fn test_junit_clean_files_pass() {
+        let smell = Smell::new(
+            PathBuf::from("./b.py"),
+            2,
+            SmellCategory::Naming,
+            "bad_naming",
+            "m",
+        );
+        let files = [PathBuf::from("./a.py"), PathBuf::from("./b.py")];
+        let xml = render(&files, &[smell]);
+        assert!(xml.contains("<testsuites name=\"lint\" tests=\"2\" failures=\"1\">"));
+        assert!(xml.contains("<testcase classname=\"lint\" name=\"a.py\" file=\"a.py\"/>"));
+        assert!(xml.contains("<testcase classname=\"lint\" name=\"b.py\" file=\"b.py\">"));
+    }
+
+    #[test]
+    fn test_junit_clean_run_has_passing_case() {
+        let xml = render(&[], &[]);
+        assert!(xml.contains("tests=\"1\" failures=\"0\""));
+        assert!(xml.contains("<testcase classname=\"lint\" name=\"lint\"/>"));
+    }
+}
@@ -0,0 +1,155 @@
+use super::{group_by_file, report_path, Report, Reporter};
+use crate::core::rules::{Smell, SmellCategory};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+/// GitHub rechaza comentarios de más de 65 536 caracteres; dejamos margen.
+pub const DEFAULT_MAX_BYTES: usize = 60_000;
+
+/// Espacio reservado para el aviso de truncado y el cierre de bloques.
+const NOTICE_RESERVE: usize = 300;
+
+pub struct MarkdownReporter;
+
+impl Reporter for MarkdownReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(render(report.smells(), DEFAULT_MAX_BYTES).as_bytes())
+    }
+}
+
+/// Reporte compacto para un comentario de PR, nunca más largo que `max_bytes`
+/// (salvo que el resumen solo ya lo sea). Los archivos que no caben se cuentan
+/// en un aviso de truncado en lugar de cortarse a medias.
+fn render(smells: &[Smell], max_bytes: usize) -> String {
+    let mut out = String::from("## lint report\n\n");
+
+    if smells.is_empty() {
+        out.push_str("No smells found. Clean architecture :sparkles:\n");
+        return out;
+    }
+
+    // 1. Resumen por categoría
+    out.push_str("| Category | Smells |\n| --- | ---: |\n");
+    for category in [
+        SmellCategory::Complexity,
+        SmellCategory::Bloat,
+        SmellCategory::Hygiene,
+        SmellCategory::Naming,
+        SmellCategory::Design,
+    ] {
+        let count = smells.iter().filter(|s| s.category == category).count();
+        let _ = writeln!(out, "| {} | {} |", category, count);
+    }
+    let _ = writeln!(out, "| **Total** | **{}** |\n", smells.len());
+
+    // 2. Lista plegable por archivo, mientras quepa
+    out.push_str("### Findings by file\n\n");
+    let files = group_by_file(smells);
+    let total_files = files.len();
+    let budget = max_bytes.saturating_sub(NOTICE_RESERVE);
+    let mut shown_files = 0;
+    let mut shown_smells = 0;
+
+    for (path, file_smells) in files {
+        let block = file_block(&report_path(path), &file_smells);
+        if out.len() + block.len() > budget {
+            break;
+        }
+        out.push_str(&block);
+        shown_files += 1;
+        shown_smells += file_smells.len();
+    }
+
+    if shown_files < total_files {
+        let _ = writeln!(
+            out,
+            "> :warning: Report truncated: {} more files ({} smells) not shown. \
+             Run `lint` locally for the full list.",
+            total_files - shown_files,
+            smells.len() - shown_smells
+        );
+    }
+    out
+}
+
+fn file_block(path: &str, smells: &[&Smell]) -> String {
+    let mut block = String::new();
+    let _ = writeln!(
+        block,
+        "<details>\n<summary><code>{}</code> — {} smells</summary>\n",
+        escape(path),
+        smells.len()
+    );
+    block.push_str("| Line | Category | Rule | Message |\n| ---: | --- | --- | --- |\n");
+    for smell in smells {
+        let _ = writeln!(
+            block,
+            "| {} | {} | `{}` | {} |",
+            smell.line,
+            smell.category,
+            smell.rule_id,
+            escape(&smell.message)
+        );
+    }
+    block.push_str("\n</details>\n\n");
+    block
+}
+
+/// Evita romper la tabla (`|`, saltos de línea) o inyectar HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn smell(path: &str, line: usize) -> Smell {
+        Smell::new(
+            PathBuf::from(path),
+            line,
+            SmellCategory::Bloat,
+            "too_many_params",
+            "Function 'f' has 5 parameters (Limit: 4) | <x>",
+        )
+    }
+
+    #[test]
+    fn test_markdown_summary_and_details() {
+        let md = render(
+            &[smell("./src/a.rs", 3), smell("./src/a.rs", 9)],
+            DEFAULT_MAX_BYTES,
+        );
+
+        assert!(md.contains("| BLOAT | 2 |"));
+        assert!(md.contains("| **Total** | **2** |"));
+        assert!(md.contains("<summary><code>src/a.rs</code> — 2 smells</summary>"));
+        assert!(md.contains("(Limit: 4) \\| &lt;x&gt; |"));
+        assert!(!md.contains("truncated"));
+    }
+
+    #[test]
+    fn test_markdown_truncates_whole_files() {
+        let smells: Vec<Smell> = (0..200)
+            .map(|i| smell(&format!("f{:03}.rs", i), 1))
+            .collect();
+        let md = render(&smells, 4_000);
+
+        assert!(md.len() <= 4_000);
+        assert!(md.contains("Report truncated:"));
+        assert_eq!(
+            md.matches("<details>").count(),
+            md.matches("</details>").count()
+        );
+    }
+
+    #[test]
+    fn test_markdown_clean_run() {
+        assert!(render(&[], DEFAULT_MAX_BYTES).contains("No smells found"));
+    }
+}
@@ -1,2 +1,415 @@
+pub mod baseline;
+pub mod checkstyle;
+pub mod concise;
 pub mod console;
+pub mod csv;
+pub mod diagnostic;
+pub mod diff;
+pub mod github;
+pub mod gitlab;
+pub mod html;
 pub mod json;
+pub mod junit;
+pub mod markdown;
+pub mod ndjson;
+pub mod sarif;
+pub mod terminal;
+mod xml;
+
+use crate::analysis::engine::Analysis;
+use crate::core::config::LintConfig;
+use crate::core::rules::Smell;
+use clap::ValueEnum;
+use console::ConsoleOptions;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use terminal::Terminal;
+
+/// Output port (see ARCHITECTURE.md): every format can write itself to any
+/// destination, be it stdout or a file.
+pub trait Reporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// What a report can show about a run: the analysis result and the effective
+/// configuration it was obtained with.
+#[derive(Clone, Copy)]
+pub struct Report<'a> {
+    pub analysis: &'a Analysis,
+    pub config: &'a LintConfig,
+    /// What stdout can display; reports written to files are always plain.
+    pub terminal: Terminal,
+    /// View selected for the console format.
+    pub console: ConsoleOptions,
+}
+
+impl Report<'_> {
+    pub fn smells(&self) -> &[Smell] {
+        &self.analysis.smells
+    }
+
+    /// Paths of the files that were analyzed, with or without smells.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.analysis.files
+    }
+
+    /// Sources analyzed from memory (`--stdin`), by path.
+    pub fn sources(&self) -> &BTreeMap<PathBuf, String> {
+        &self.analysis.sources
+    }
+}
+
+/// Analyzed text of `path`: the in-memory one if there is one, otherwise the one on disk.
+pub fn read_source<'a>(
+    sources: &'a BTreeMap<PathBuf, String>,
+    path: &Path,
+) -> Option<Cow<'a, str>> {
+    match sources.get(path) {
+        Some(source) => Some(Cow::Borrowed(source)),
+        None => fs::read_to_string(path).ok().map(Cow::Owned),
+    }
+}
+
+/// Output formats available in `--format` and `--output`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable summary and table
+    Console,
+    /// rustc-style diagnostics with source snippets
+    Diagnostic,
+    /// One `path:line:col: [CATEGORY/rule] message` line per smell
+    Concise,
+    /// Machine-readable JSON report
+    Json,
+    /// Newline-delimited JSON, streamed one smell per line as files finish
+    Ndjson,
+    /// SARIF 2.1.0 for code-scanning dashboards
+    Sarif,
+    /// Checkstyle XML (Jenkins warnings-ng and friends)
+    Checkstyle,
+    /// JUnit XML, one test case per file
+    Junit,
+    /// GitHub Actions workflow commands (inline annotations)
+    Github,
+    /// GitLab Code Quality JSON
+    Gitlab,
+    /// Self-contained HTML page with per-file source views
+    Html,
+    /// Markdown summary sized for pull request comments
+    Markdown,
+    /// Spreadsheet-friendly CSV, one row per smell
+    Csv,
+    /// CSV with one row per function: lines, params, nesting, complexity
+    MetricsCsv,
+}
+
+impl OutputFormat {
+    /// Machine-readable formats must not be mixed with progress logs.
+    pub fn is_machine_readable(self) -> bool {
+        !matches!(self, OutputFormat::Console | OutputFormat::Diagnostic)
+    }
+
+    /// Whether the report needs the per-function metrics (computed separately).
+    pub fn needs_metrics(self) -> bool {
+        self == OutputFormat::MetricsCsv
+    }
+
+    pub fn reporter(self) -> Box<dyn Reporter> {
+        match self {
+            OutputFormat::Console => Box::new(console::ConsoleReporter),
+            OutputFormat::Diagnostic => Box::new(diagnostic::DiagnosticReporter),
+            OutputFormat::Concise => Box::new(concise::ConciseReporter),
+            OutputFormat::Json => Box::new(json::JsonReporter),
+            OutputFormat::Ndjson => Box::new(ndjson::NdjsonReporter),
+            OutputFormat::Sarif => Box::new(sarif::SarifReporter),
+            OutputFormat::Checkstyle => Box::new(checkstyle::CheckstyleReporter),
+            OutputFormat::Junit => Box::new(junit::JunitReporter),
+            OutputFormat::Github => Box::new(github::GithubReporter),
+            OutputFormat::Gitlab => Box::new(gitlab::GitlabReporter),
+            OutputFormat::Html => Box::new(html::HtmlReporter),
+            OutputFormat::Markdown => Box::new(markdown::MarkdownReporter),
+            OutputFormat::Csv => Box::new(csv::CsvReporter),
+            OutputFormat::MetricsCsv => Box::new(csv::MetricsCsvReporter),
+        }
+    }
+}
+
+/// A `--output <format>[=<file>]` target. Without a file, it goes to stdout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTarget {
+    pub format: OutputFormat,
+    pub path: Option<PathBuf>,
+}
+
+impl FromStr for OutputTarget {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let (name, path) = match spec.split_once('=') {
+            Some((name, "")) => return Err(format!("missing file name after '{}='", name)),
+            Some((name, path)) => (name, Some(PathBuf::from(path))),
+            None => (spec, None),
+        };
+        let format = OutputFormat::from_str(name, true)?;
+        Ok(Self { format, path })
+    }
+}
+
+impl std::fmt::Display for OutputTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self
+            .format
+            .to_possible_value()
+            .map(|v| v.get_name().to_string());
+        write!(f, "{}", name.unwrap_or_default())?;
+        match &self.path {
+            Some(path) => write!(f, " ({})", path.display()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl OutputTarget {
+    /// Writes the report to its destination. Files are written atomically: no
+    /// other process can ever read a half-written report.
+    pub fn emit(&self, report: &Report) -> io::Result<()> {
+        let reporter = self.format.reporter();
+        let terminal = match self.path {
+            Some(_) => Terminal::PLAIN,
+            None => report.terminal,
+        };
+        let report = &Report {
+            terminal,
+            ..*report
+        };
+        // `colored` decides globally; reports are written one at a time.
+        colored::control::set_override(terminal.color);
+
+        match &self.path {
+            None => {
+                let stdout = io::stdout();
+                let mut out = stdout.lock();
+                reporter.write_report(report, &mut out)?;
+                out.flush()
+            }
+            Some(path) => write_atomically(path, |out| reporter.write_report(report, out)),
+        }
+    }
+}
+
+/// Writes to a temporary file next to `path` and renames it when done, so that
+/// `path` holds either the previous report or the complete new one, never a partial one.
+fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut file = AtomicFile::create(path)?;
+    write(&mut file)?;
+    file.commit()
+}
+
+/// Temporary files created by this process, so that each gets its own name.
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Temporary file that only replaces its destination on `commit`. If it is
+/// dropped before (because of an error), the temporary file is deleted.
+pub struct AtomicFile {
+    path: PathBuf,
+    tmp: PathBuf,
+    out: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let name = path.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "output path has no file name")
+        })?;
+        // The pid keeps other processes out, the counter other files of this
+        // one; `create_new` never reuses a file left behind by a crashed run.
+        let (tmp, file) = loop {
+            let tmp = dir.join(format!(
+                ".{}.{}.{}.tmp",
+                name.to_string_lossy(),
+                std::process::id(),
+                TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+            ));
+            match File::options().write(true).create_new(true).open(&tmp) {
+                Ok(file) => break (tmp, file),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        };
+        let out = BufWriter::new(file);
+        Ok(Self {
+            path: path.to_path_buf(),
+            tmp,
+            out: Some(out),
+        })
+    }
+
+    /// Flushes the content to disk and renames the temporary file over the destination.
+    pub fn commit(mut self) -> io::Result<()> {
+        if let Some(out) = self.out.take() {
+            let file = out.into_inner().map_err(|e| e.into_error())?;
+            file.sync_all()?;
+            fs::rename(&self.tmp, &self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.out {
+            Some(out) => out.write(buf),
+            None => Err(io::Error::other("file already committed")),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.out {
+            Some(out) => out.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        // After a successful `commit` the temporary file is gone and this does nothing.
+        let _ = fs::remove_file(&self.tmp);
+    }
+}
+
+/// Path normalized for reports: `/` separators and no `./` prefix.
+pub fn report_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+/// Groups the smells by file, in path order.
+pub fn group_by_file(smells: &[Smell]) -> BTreeMap<&Path, Vec<&Smell>> {
+    let mut files: BTreeMap<&Path, Vec<&Smell>> = BTreeMap::new();
+    for smell in smells {
+        files.entry(&smell.file_path).or_default().push(smell);
+    }
+    files
+}
+
+/// Stable fingerprint of each smell, in the same order as `smells`.
+///
+/// Identifies the smell by file, rule and enclosing symbol, without the line
+/// number or the message: so it survives code changes above it, a function
+/// growing (the message carries the measured value and the limit) and a change
+/// of thresholds. Smells of the same rule within the same symbol are told apart
+/// by their occurrence number.
+pub fn fingerprints(smells: &[Smell]) -> Vec<String> {
+    let mut seen: HashMap<(String, &str, &str), usize> = HashMap::new();
+    smells
+        .iter()
+        .map(|smell| {
+            let path = report_path(&smell.file_path);
+            let symbol = smell.symbol.as_deref().unwrap_or("");
+            let key = (path, smell.rule_id.as_str(), symbol);
+            let occurrence = seen.entry(key.clone()).or_insert(0);
+            *occurrence += 1;
+            let raw = format!("{}\0{}\0{}\0{}", key.0, key.1, key.2, occurrence);
+            format!("{:016x}", fnv1a(raw.as_bytes()))
+        })
+        .collect()
+}
+
+/// 64-bit FNV-1a: stable across Rust versions, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rules::SmellCategory;
+
+    fn smell(path: &str, line: usize, message: &str) -> Smell {
+        Smell::new(
+            PathBuf::from(path),
+            line,
+            SmellCategory::Naming,
+            "short_variable",
+            message,
+        )
+    }
+
+    #[test]
+    fn test_fingerprints_survive_line_shifts() {
+        let before = fingerprints(&[smell("./a.py", 3, "Variable 'd'")]);
+        let after = fingerprints(&[smell("a.py", 10, "Variable 'd'")]);
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_fingerprints_are_unique_for_repeated_smells() {
+        let prints = fingerprints(&[
+            smell("a.py", 3, "Variable 'd'"),
+            smell("a.py", 8, "Variable 'd'"),
+        ]);
+        assert_ne!(prints[0], prints[1]);
+    }
+
+    #[test]
+    fn test_output_target_parsing() {
+        assert_eq!(
+            "sarif=out/report.sarif".parse::<OutputTarget>(),
+            Ok(OutputTarget {
+                format: OutputFormat::Sarif,
+                path: Some(PathBuf::from("out/report.sarif")),
+            })
+        );
+        assert_eq!(
+            "CONSOLE".parse::<OutputTarget>(),
+            Ok(OutputTarget {
+                format: OutputFormat::Console,
+                path: None,
+            })
+        );
+        assert!("json=".parse::<OutputTarget>().is_err());
+        assert!("yaml=x.yml".parse::<OutputTarget>().is_err());
+    }
+
+    #[test]
+    fn test_atomic_write_replaces_file_and_cleans_up() {
+        let dir = std::env::temp_dir().join(format!("lint-atomic-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("report.json");
+        fs::write(&path, "old").unwrap();
+
+        let failed = write_atomically(&path, |out| {
+            out.write_all(b"partial")?;
+            Err(io::Error::other("boom"))
+        });
+        assert!(failed.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        // Two files open for the same destination don't share a temp file.
+        let first = AtomicFile::create(&path).unwrap();
+        let second = AtomicFile::create(&path).unwrap();
+        assert_ne!(first.tmp, second.tmp);
+        drop((first, second));
+
+        write_atomically(&path, |out| out.write_all(b"new")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1, "no temp files left");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
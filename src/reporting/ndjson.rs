@@ -0,0 +1,248 @@
+use super::json::{empty_category_counts, ToolInfo, SCHEMA_VERSION};
+use super::{AtomicFile, Report, Reporter};
+use crate::analysis::engine::{Analysis, AnalysisError};
+use crate::analysis::languages::Language;
+use crate::core::rules::{Smell, SmellCategory};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Un registro por línea; `type` indica cuál es. El flujo siempre empieza con
+/// `header` y termina con `summary`, con un `smell` por línea entre medias.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record<'a> {
+    Header {
+        schema_version: u32,
+        tool: ToolInfo,
+    },
+    Smell(&'a Smell),
+    Summary {
+        total_smells: usize,
+        by_category: &'a BTreeMap<SmellCategory, usize>,
+        files_analyzed: usize,
+        by_language: &'a BTreeMap<Language, usize>,
+        unsupported_files: &'a BTreeMap<&'static str, usize>,
+        duration_ms: u64,
+        errors: &'a [AnalysisError],
+    },
+}
+
+fn write_record(out: &mut dyn Write, record: &Record) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, record)?;
+    writeln!(out)
+}
+
+fn header() -> Record<'static> {
+    Record::Header {
+        schema_version: SCHEMA_VERSION,
+        tool: ToolInfo::current(),
+    }
+}
+
+fn summary<'a>(
+    analysis: &'a Analysis,
+    total_smells: usize,
+    by_category: &'a BTreeMap<SmellCategory, usize>,
+) -> Record<'a> {
+    Record::Summary {
+        total_smells,
+        by_category,
+        files_analyzed: analysis.files_analyzed(),
+        by_language: &analysis.analyzed,
+        unsupported_files: &analysis.unsupported,
+        duration_ms: analysis.duration.as_millis() as u64,
+        errors: &analysis.errors,
+    }
+}
+
+/// Escribe el reporte completo de una vez, cuando no se pudo transmitir en vivo
+/// (por ejemplo, con `--stdin`).
+pub struct NdjsonReporter;
+
+impl Reporter for NdjsonReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        let mut by_category = empty_category_counts();
+        write_record(out, &header())?;
+        for smell in report.smells() {
+            *by_category.entry(smell.category).or_insert(0) += 1;
+            write_record(out, &Record::Smell(smell))?;
+        }
+        let smells = report.smells().len();
+        write_record(out, &summary(report.analysis, smells, &by_category))
+    }
+}
+
+enum Destination {
+    Stdout(io::Stdout),
+    File(AtomicFile),
+}
+
+impl Destination {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Destination::Stdout(out) => out,
+            Destination::File(out) => out,
+        }
+    }
+}
+
+struct StreamState {
+    dest: Destination,
+    total_smells: usize,
+    by_category: BTreeMap<SmellCategory, usize>,
+    /// Primer error de escritura; los siguientes archivos ya no se escriben.
+    error: Option<io::Error>,
+}
+
+/// Salida NDJSON en vivo: cada archivo se escribe en cuanto termina su análisis,
+/// sin acumular los smells en memoria.
+pub struct NdjsonStream {
+    state: Mutex<StreamState>,
+}
+
+impl NdjsonStream {
+    /// Abre el destino (stdout si no hay archivo) y escribe la cabecera.
+    pub fn open(path: Option<&Path>) -> io::Result<Self> {
+        let mut dest = match path {
+            None => Destination::Stdout(io::stdout()),
+            Some(path) => Destination::File(AtomicFile::create(path)?),
+        };
+        write_record(dest.writer(), &header())?;
+        Ok(Self {
+            state: Mutex::new(StreamState {
+                dest,
+                total_smells: 0,
+                by_category: empty_category_counts(),
+                error: None,
+            }),
+        })
+    }
+
+    /// Escribe los smells de un archivo. Se llama desde los hilos de análisis;
+    /// el bloqueo mantiene juntas las líneas de cada archivo.
+    pub fn file_done(&self, smells: &[Smell]) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.error.is_some() {
+            return;
+        }
+        let state = &mut *state;
+        let mut write = || {
+            for smell in smells {
+                *state.by_category.entry(smell.category).or_insert(0) += 1;
+                write_record(state.dest.writer(), &Record::Smell(smell))?;
+            }
+            state.total_smells += smells.len();
+            state.dest.writer().flush()
+        };
+        if let Err(e) = write() {
+            state.error = Some(e);
+        }
+    }
+
+    /// Escribe el resumen final y, si el destino es un archivo, lo publica.
+    pub fn finish(self, analysis: &Analysis) -> io::Result<()> {
+        let mut state = self
+            .state
+            .into_inner()
+            .map_err(|_| io::Error::other("NDJSON writer panicked"))?;
+        if let Some(e) = state.error {
+            return Err(e);
+        }
+        let record = summary(analysis, state.total_smells, &state.by_category);
+        write_record(state.dest.writer(), &record)?;
+        match state.dest {
+            Destination::Stdout(mut out) => out.flush(),
+            Destination::File(file) => file.commit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporting::terminal::Terminal;
+    use serde_json::Value;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn smell(path: &str, line: usize) -> Smell {
+        Smell::new(
+            PathBuf::from(path),
+            line,
+            SmellCategory::Hygiene,
+            "todo_comment",
+            "Found technical debt anchor (TODO/FIXME).",
+        )
+    }
+
+    fn parse_lines(text: &str) -> Vec<Value> {
+        text.lines()
+            .map(|line| serde_json::from_str(line).expect("every line is a JSON document"))
+            .collect()
+    }
+
+    #[test]
+    fn test_stream_writes_header_smells_and_summary() {
+        let dir = std::env::temp_dir().join(format!("lint-ndjson-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("report.ndjson");
+
+        let stream = NdjsonStream::open(Some(&path)).unwrap();
+        stream.file_done(&[smell("a.py", 1), smell("a.py", 4)]);
+        stream.file_done(&[]);
+        stream.file_done(&[smell("b.py", 2)]);
+        assert!(!path.exists(), "the report only appears once complete");
+
+        let analysis = Analysis {
+            analyzed: BTreeMap::from([(Language::Python, 3)]),
+            ..Analysis::default()
+        };
+        stream.finish(&analysis).unwrap();
+
+        let records = parse_lines(&fs::read_to_string(&path).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[0]["type"], "header");
+        assert_eq!(records[0]["schema_version"], SCHEMA_VERSION);
+        assert_eq!(records[1]["type"], "smell");
+        assert_eq!(records[1]["file_path"], "a.py");
+        assert_eq!(records[3]["line"], 2);
+
+        let summary = &records[4];
+        assert_eq!(summary["type"], "summary");
+        assert_eq!(summary["total_smells"], 3);
+        assert_eq!(summary["by_category"]["Hygiene"], 3);
+        assert_eq!(summary["files_analyzed"], 3);
+    }
+
+    #[test]
+    fn test_buffered_reporter_matches_stream_layout() {
+        let analysis = Analysis {
+            smells: vec![smell("a.py", 1)],
+            ..Analysis::default()
+        };
+        let config = Default::default();
+        let report = Report {
+            analysis: &analysis,
+            config: &config,
+            terminal: Terminal::PLAIN,
+            console: Default::default(),
+        };
+        let mut out = Vec::new();
+        NdjsonReporter.write_report(&report, &mut out).unwrap();
+
+        let records = parse_lines(&String::from_utf8(out).unwrap());
+        let kinds: Vec<&str> = records
+            .iter()
+            .map(|r| r["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, ["header", "smell", "summary"]);
+        assert_eq!(records[2]["total_smells"], 1);
+    }
+}
@@ -0,0 +1,491 @@
+use super::{report_path, Report, Reporter};
+use crate::core::rules::{rule_info, Metric, Severity, Smell};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Base de las rutas relativas: el directorio desde el que se lanzó el análisis.
+const SRCROOT: &str = "%SRCROOT%";
+
+/// Lo que puede ir sin codificar en un segmento de ruta (`unreserved` en RFC 3986).
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Raíz de un log SARIF 2.1.0 (un único `run`).
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    /// Las columnas de `Smell` cuentan caracteres, no unidades UTF-16.
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+/// Descriptor de una regla (`tool.driver.rules[]`).
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: String,
+    name: String,
+    short_description: Text,
+    default_configuration: Configuration,
+    properties: Properties,
+}
+
+#[derive(Serialize)]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Text,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<SarifFix>,
+    properties: Properties,
+}
+
+/// Corrección propuesta: reemplazos por offset de bytes sobre el archivo.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+    description: Text,
+    artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: ByteRegion,
+    inserted_content: Text,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ByteRegion {
+    byte_offset: usize,
+    byte_length: usize,
+}
+
+#[derive(Serialize)]
+struct Text {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    logical_locations: Vec<LogicalLocation>,
+}
+
+/// Símbolo que contiene el resultado (función, tipo o módulo).
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    fully_qualified_name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+/// Rango del resultado. Sin fin conocido, solo se indica dónde empieza.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+    /// Exclusiva, como `Smell::end_column`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
+}
+
+/// Propiedades libres; las categorías viajan como `tags`.
+#[derive(Serialize)]
+struct Properties {
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
+    /// Medida y límite, para que los dashboards vean cuánto se supera.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metric: Option<Metric>,
+}
+
+pub struct SarifReporter;
+
+impl Reporter for SarifReporter {
+    fn write_report(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, &build_log(report.smells()))?;
+        writeln!(out)
+    }
+}
+
+fn build_log(smells: &[Smell]) -> SarifLog {
+    // 1. Un descriptor por cada rule_id presente, en orden estable
+    let rule_ids: Vec<&str> = smells
+        .iter()
+        .map(|s| s.rule_id.as_str())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let rules = rule_ids.iter().map(|id| describe_rule(id)).collect();
+
+    // 2. Resultados apuntando a su descriptor por índice
+    let results = smells
+        .iter()
+        .map(|smell| SarifResult {
+            rule_id: smell.rule_id.clone(),
+            rule_index: rule_ids
+                .binary_search(&smell.rule_id.as_str())
+                .unwrap_or_default(),
+            level: level(smell.severity()),
+            message: Text {
+                text: smell.message.clone(),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: artifact_location(&smell.file_path),
+                    region: region(smell),
+                },
+                logical_locations: smell
+                    .symbol
+                    .iter()
+                    .map(|symbol| LogicalLocation {
+                        fully_qualified_name: symbol.clone(),
+                    })
+                    .collect(),
+            }],
+            fixes: smell
+                .fix
+                .iter()
+                .map(|fix| SarifFix {
+                    description: Text {
+                        text: fix.description.clone(),
+                    },
+                    artifact_changes: vec![ArtifactChange {
+                        artifact_location: artifact_location(&smell.file_path),
+                        replacements: fix
+                            .edits
+                            .iter()
+                            .map(|edit| Replacement {
+                                deleted_region: ByteRegion {
+                                    byte_offset: edit.start,
+                                    byte_length: edit.end - edit.start,
+                                },
+                                inserted_content: Text {
+                                    text: edit.replacement.clone(),
+                                },
+                            })
+                            .collect(),
+                    }],
+                })
+                .collect(),
+            properties: Properties {
+                tags: vec![smell.category.to_string().to_lowercase()],
+                context: smell.context.clone(),
+                metric: smell.metric,
+            },
+        })
+        .collect();
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    rules,
+                },
+            },
+            column_kind: "unicodeCodePoints",
+            results,
+        }],
+    }
+}
+
+fn region(smell: &Smell) -> Region {
+    let start = (smell.line.max(1), smell.column.max(1));
+    let end = (smell.end_line, smell.end_column);
+    let spanned = end > start;
+    Region {
+        start_line: start.0,
+        start_column: start.1,
+        end_line: spanned.then_some(end.0),
+        end_column: spanned.then_some(end.1),
+    }
+}
+
+fn describe_rule(id: &str) -> ReportingDescriptor {
+    let info = rule_info(id);
+    ReportingDescriptor {
+        id: id.to_string(),
+        name: id.to_string(),
+        short_description: Text {
+            text: info.map_or(id, |r| r.summary).to_string(),
+        },
+        default_configuration: Configuration {
+            level: level(info.map_or(Severity::Warning, |r| r.severity)),
+        },
+        properties: Properties {
+            tags: info
+                .map(|r| vec![r.category.to_string().to_lowercase()])
+                .unwrap_or_default(),
+            context: None,
+            metric: None,
+        },
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "note",
+        Severity::Warning => "warning",
+    }
+}
+
+/// Ubicación de un archivo como URI. Las rutas relativas se resuelven contra
+/// `%SRCROOT%`; las absolutas (también `C:\...` y `\\servidor\...` de
+/// Windows) pasan a ser URIs `file:`. Cada segmento se codifica con `%XX`, así
+/// que `#`, `?`, espacios o caracteres no ASCII no rompen la URI.
+fn artifact_location(path: &Path) -> ArtifactLocation {
+    let path = report_path(path);
+    let encode = |path: &str| {
+        path.split('/')
+            .map(|segment| utf8_percent_encode(segment, SEGMENT).to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+    let file = |uri: String| ArtifactLocation {
+        uri,
+        uri_base_id: None,
+    };
+
+    let bytes = path.as_bytes();
+    if let Some(unc) = path.strip_prefix("//") {
+        file(format!("file://{}", encode(unc)))
+    } else if bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && bytes[2] == b'/'
+    {
+        file(format!("file:///{}{}", &path[..2], encode(&path[2..])))
+    } else if path.starts_with('/') {
+        file(format!("file://{}", encode(&path)))
+    } else {
+        ArtifactLocation {
+            uri: encode(&path),
+            uri_base_id: Some(SRCROOT),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fix::{Edit, Fix};
+    use crate::core::rules::{SmellCategory, Span};
+    use serde_json::Value;
+    use std::path::PathBuf;
+
+    /// Esquema oficial de OASIS (SARIF 2.1.0, Errata 01, OASIS Standard), sin
+    /// modificar. No está en el repo: hay que descargarlo a `UPSTREAM_SCHEMA_FILE`.
+    const UPSTREAM_SCHEMA_URL: &str =
+        "https://docs.oasis-open.org/sarif/sarif/v2.1.0/errata01/os/schemas/sarif-schema-2.1.0.json";
+    const UPSTREAM_SCHEMA_FILE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/reporting/sarif-schema-2.1.0.json"
+    );
+
+    fn sample() -> Vec<Smell> {
+        vec![
+            Smell::new(
+                PathBuf::from("./src/main.rs"),
+                12,
+                SmellCategory::Bloat,
+                "long_function",
+                "Function 'main' is 80 lines long (Limit: 40).",
+            )
+            .with_context("Lines: 80".to_string())
+            .with_metric("lines", 80, 40)
+            .with_symbol("crate::main")
+            .with_span(Span {
+                line: 12,
+                column: 5,
+                end_line: 91,
+                end_column: 2,
+            }),
+            Smell::new(
+                PathBuf::from("scripts/my tool.py"),
+                3,
+                SmellCategory::Hygiene,
+                "todo_comment",
+                "Found technical debt anchor (TODO/FIXME).",
+            )
+            .with_fix(Fix::new(
+                "Remove trailing whitespace",
+                vec![Edit::delete(40, 42)],
+            )),
+            Smell::new(
+                PathBuf::from("src/lib.rs"),
+                7,
+                SmellCategory::Bloat,
+                "long_function",
+                "Function 'run' is 50 lines long (Limit: 40).",
+            ),
+        ]
+    }
+
+    /// Lo que un esquema no expresa: `ruleIndex` apunta al descriptor de `ruleId`.
+    fn assert_rule_indexes(log: &Value) {
+        let rules = log["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        for result in log["runs"][0]["results"].as_array().unwrap() {
+            let index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(rules[index]["id"], result["ruleId"]);
+        }
+    }
+
+    #[test]
+    #[ignore = "needs the upstream schema in src/reporting/sarif-schema-2.1.0.json"]
+    fn test_sarif_conforms_to_upstream_schema() {
+        let schema = std::fs::read_to_string(UPSTREAM_SCHEMA_FILE).unwrap_or_else(|_| {
+            panic!(
+                "download {} to {}",
+                UPSTREAM_SCHEMA_URL, UPSTREAM_SCHEMA_FILE
+            )
+        });
+        let schema: Value = serde_json::from_str(&schema).unwrap();
+        let validator = jsonschema::options()
+            .should_validate_formats(true)
+            .build(&schema)
+            .unwrap();
+        for smells in [sample(), Vec::new()] {
+            let log = serde_json::to_value(build_log(&smells)).unwrap();
+            let errors: Vec<String> = validator
+                .iter_errors(&log)
+                .map(|error| error.to_string())
+                .collect();
+            assert!(errors.is_empty(), "{:#?}", errors);
+        }
+    }
+
+    #[test]
+    fn test_sarif_log_structure() {
+        let log = serde_json::to_value(build_log(&sample())).unwrap();
+        assert_rule_indexes(&log);
+        assert_eq!(log["runs"][0]["columnKind"], "unicodeCodePoints");
+
+        let rules = log["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules.len(), 2, "one descriptor per rule_id");
+
+        let first = &log["runs"][0]["results"][0];
+        assert_eq!(first["ruleId"], "long_function");
+        assert_eq!(first["level"], "warning");
+        assert_eq!(first["properties"]["tags"][0], "bloat");
+        assert_eq!(first["properties"]["metric"]["value"], 80);
+        assert_eq!(first["properties"]["metric"]["limit"], 40);
+        assert_eq!(
+            first["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "crate::main"
+        );
+        assert_eq!(
+            first["locations"][0]["physicalLocation"]["artifactLocation"],
+            serde_json::json!({ "uri": "src/main.rs", "uriBaseId": "%SRCROOT%" })
+        );
+        assert_eq!(
+            first["locations"][0]["physicalLocation"]["region"],
+            serde_json::json!({ "startLine": 12, "startColumn": 5, "endLine": 91, "endColumn": 2 })
+        );
+        let second = &log["runs"][0]["results"][1];
+        assert_eq!(second["level"], "note");
+        assert_eq!(
+            second["locations"][0]["physicalLocation"]["region"],
+            serde_json::json!({ "startLine": 3, "startColumn": 1 }),
+            "without a known end, only the start"
+        );
+        let replacement = &second["fixes"][0]["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["deletedRegion"]["byteLength"], 2);
+        assert!(first.get("fixes").is_none());
+    }
+
+    #[test]
+    fn test_artifact_uris_are_percent_encoded() {
+        let uri = |path: &str| artifact_location(Path::new(path)).uri;
+        assert_eq!(uri("scripts/my tool#1?.py"), "scripts/my%20tool%231%3F.py");
+        assert_eq!(uri("docs/100%/año.py"), "docs/100%25/a%C3%B1o.py");
+        assert_eq!(uri("/tmp/a b.rs"), "file:///tmp/a%20b.rs");
+        assert_eq!(uri("C:\\work\\a.rs"), "file:///C:/work/a.rs");
+        assert_eq!(uri("\\\\server\\share\\a.rs"), "file://server/share/a.rs");
+        assert!(artifact_location(Path::new("/tmp/a.rs"))
+            .uri_base_id
+            .is_none());
+    }
+
+    #[test]
+    fn test_empty_report_has_a_run() {
+        let log = serde_json::to_value(build_log(&[])).unwrap();
+        assert_rule_indexes(&log);
+        assert!(log["runs"][0]["results"].as_array().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use clap::ValueEnum;
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, IsTerminal};
+
+/// Values of `--color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Color only when writing to a terminal, unless NO_COLOR/CLICOLOR_FORCE say otherwise
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color an output that is (or is not) a terminal.
+    pub fn resolve(self, is_terminal: bool) -> bool {
+        self.resolve_with(is_terminal, |name| env::var_os(name))
+    }
+
+    /// Same precedence as `colored`: `CLICOLOR_FORCE` (other than `0`) beats
+    /// `NO_COLOR`, and both beat terminal detection. `--color` beats everything.
+    fn resolve_with(self, is_terminal: bool, var: impl Fn(&str) -> Option<OsString>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let set = |name: &str| var(name).filter(|value| !value.is_empty());
+                if set("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+                    true
+                } else if set("NO_COLOR").is_some() {
+                    false
+                } else {
+                    is_terminal
+                }
+            }
+        }
+    }
+}
+
+/// What the destination of a report can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Terminal {
+    /// ANSI color and style sequences are allowed.
+    pub color: bool,
+    /// An interactive terminal: there is a width to respect and tables are worth
+    /// drawing. Otherwise a plain layout suited to logs and pipes is used.
+    pub interactive: bool,
+}
+
+impl Terminal {
+    /// A destination with no capabilities: files, pipes, CI logs.
+    pub const PLAIN: Terminal = Terminal {
+        color: false,
+        interactive: false,
+    };
+
+    pub fn stdout(choice: ColorChoice) -> Self {
+        let interactive = io::stdout().is_terminal();
+        Self {
+            color: choice.resolve(interactive),
+            interactive,
+        }
+    }
+
+    pub fn stderr(choice: ColorChoice) -> Self {
+        let interactive = io::stderr().is_terminal();
+        Self {
+            color: choice.resolve(interactive),
+            interactive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(choice: ColorChoice, tty: bool, vars: &[(&str, &str)]) -> bool {
+        choice.resolve_with(tty, |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| OsString::from(value))
+        })
+    }
+
+    #[test]
+    fn test_color_resolution_order() {
+        use ColorChoice::*;
+
+        assert!(resolve(Auto, true, &[]));
+        assert!(!resolve(Auto, false, &[]));
+        assert!(!resolve(Auto, true, &[("NO_COLOR", "1")]));
+        assert!(
+            resolve(Auto, true, &[("NO_COLOR", "")]),
+            "empty NO_COLOR is unset"
+        );
+        assert!(resolve(Auto, false, &[("CLICOLOR_FORCE", "1")]));
+        assert!(!resolve(Auto, false, &[("CLICOLOR_FORCE", "0")]));
+        assert!(resolve(
+            Auto,
+            false,
+            &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]
+        ));
+
+        assert!(resolve(Always, false, &[("NO_COLOR", "1")]));
+        assert!(!resolve(Never, true, &[("CLICOLOR_FORCE", "1")]));
+    }
+}
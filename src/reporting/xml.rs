@@ -0,0 +1,36 @@
+/// Escapa texto para usarlo tanto en atributos como en contenido XML (o HTML).
+/// Saltos de línea y tabuladores van como referencias, que sobreviven a la
+/// normalización de atributos; los demás caracteres de control se descartan.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            '\t' => out.push_str("&#9;"),
+            '\r' => out.push(' '),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("a < b && \"c\" > 'd'"),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; &apos;d&apos;"
+        );
+        assert_eq!(escape("line\nbreak\u{1b}[0m"), "line&#10;break[0m");
+        assert_eq!(escape("\tindent"), "&#9;indent");
+    }
+}